@@ -0,0 +1,22 @@
+//! Anchor paths shared by the integrity and coordinator zomes.
+//!
+//! The HDI has no `hash_path`, so integrity zomes can't build the `Path` of an anchor
+//! to check the links attached to it. `AnchorPath` serializes like the HDK's `Path`,
+//! so it hashes to the same anchors and makes the same link tags as `ensure()`.
+//!
+//! This can't live in playset_integrity: a zome depending on another integrity zome
+//! would link its `validate` and `entry_defs` externs twice.
+
+#![allow(unused_doc_comments)]
+
+mod path;
+mod shard;
+#[cfg(feature = "coordinator")]
+mod listing;
+#[cfg(feature = "test_utils")]
+pub mod test_utils;
+
+pub use path::*;
+pub use shard::*;
+#[cfg(feature = "coordinator")]
+pub use listing::*;
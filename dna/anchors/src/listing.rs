@@ -0,0 +1,117 @@
+use hdk::prelude::*;
+use hdk::hash_path::path::Component;
+use zome_utils::*;
+
+use crate::*;
+
+
+/// Key of the pseudo-shard holding the links created on a root anchor before sharding
+const LEGACY_SHARD: &str = "";
+
+
+/// Position of the next page: a shard key and the offset of its first link in that shard
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageCursor {
+  pub shard: String,
+  pub offset: u32,
+}
+
+/// Page request, starting from the first page when there is no cursor
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPageInput {
+  pub maybe_cursor: Option<PageCursor>,
+  pub limit: u32,
+}
+
+/// Items of a page, and the cursor of the next page if there are more
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageOutput<T> {
+  pub items: Vec<T>,
+  pub maybe_next_cursor: Option<PageCursor>,
+}
+
+
+impl From<AnchorPath> for Path {
+  fn from(anchor: AnchorPath) -> Self {
+    Path::from(anchor.components().iter()
+      .map(|component| Component::from(component.as_bytes().to_vec()))
+      .collect::<Vec<Component>>())
+  }
+}
+
+impl AnchorPath {
+  pub fn typed<L: ListingLinkType>(self, link_type: L) -> ExternResult<TypedPath> {
+    Ok(TypedPath::new(link_type.try_into()?, Path::from(self)))
+  }
+}
+
+
+/// Link a new entry from the shard anchor of its name
+pub fn link_to_shard<L: ListingLinkType>(root_path: &str, name: &str, link_type: L, eh: EntryHash) -> ExternResult<ActionHash> {
+  let path = shard_path(root_path, &name_shard(name)).typed(link_type)?;
+  path.ensure()?;
+  create_link(path.path_entry_hash()?, eh, path.link_type, LinkTag::from(()))
+}
+
+
+/// Listing links of every entry under a root anchor
+pub fn get_all_links<L: ListingLinkType>(root_path: &str, link_type: L) -> ExternResult<Vec<Link>> {
+  let root = AnchorPath::from(root_path).typed(link_type)?;
+  let mut links = Vec::new();
+  for shard in get_shards(&root)? {
+    links.append(&mut get_shard_links(root_path, link_type, &shard)?);
+  }
+  Ok(links)
+}
+
+
+/// Listing links of one page and the cursor of the next page, if any
+pub fn get_page_links<L: ListingLinkType>(root_path: &str, link_type: L, input: &GetPageInput) -> ExternResult<(Vec<Link>, Option<PageCursor>)> {
+  if input.limit == 0 {
+    return zome_error!("Page limit must be at least 1");
+  }
+  let root = AnchorPath::from(root_path).typed(link_type)?;
+  let (start_shard, start_offset) = match &input.maybe_cursor {
+    Some(cursor) => (cursor.shard.clone(), cursor.offset as usize),
+    None => (LEGACY_SHARD.to_string(), 0),
+  };
+  let mut page = Vec::new();
+  for shard in get_shards(&root)?.into_iter().filter(|shard| *shard >= start_shard) {
+    let offset = if shard == start_shard { start_offset } else { 0 };
+    for (index, link) in get_shard_links(root_path, link_type, &shard)?.into_iter().enumerate().skip(offset) {
+      if page.len() >= input.limit as usize {
+        return Ok((page, Some(PageCursor { shard, offset: index as u32 })));
+      }
+      page.push(link);
+    }
+  }
+  Ok((page, None))
+}
+
+
+/// Sorted shard keys of a root anchor, starting with the legacy pseudo-shard
+fn get_shards(root: &TypedPath) -> ExternResult<Vec<String>> {
+  let mut shards: Vec<String> = root.children_paths()?
+    .into_iter()
+    .filter_map(|child| child.path.leaf().and_then(|leaf| String::try_from(leaf).ok()))
+    .collect();
+  shards.sort();
+  shards.dedup();
+  shards.insert(0, LEGACY_SHARD.to_string());
+  Ok(shards)
+}
+
+
+/// Listing links of a shard, oldest first so offsets stay stable as entries are added
+fn get_shard_links<L: ListingLinkType>(root_path: &str, link_type: L, shard: &str) -> ExternResult<Vec<Link>> {
+  let mut links = if shard == LEGACY_SHARD {
+    get_links(AnchorPath::from(root_path).path_entry_hash()?, L::legacy(), None)?
+  } else {
+    get_links(shard_path(root_path, shard).path_entry_hash()?, link_type, None)?
+  };
+  links.sort_by_key(|link| link.timestamp);
+  Ok(links)
+}
@@ -0,0 +1,164 @@
+use hdi::prelude::*;
+
+
+/// Bytes of the entry hashed by `anchor_root_hash()`
+const ROOT: [u8; 2] = [0x00, 0x01];
+
+
+/// warning: mirror of hdk::hash_path::path::Component, must serialize the same way
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize, SerializedBytes)]
+#[repr(transparent)]
+pub struct AnchorComponent(#[serde(with = "serde_bytes")] Vec<u8>);
+
+impl From<Vec<u8>> for AnchorComponent {
+  fn from(bytes: Vec<u8>) -> Self {
+    Self(bytes)
+  }
+}
+
+/// Each char is stored as the little endian bytes of its u32, like the HDK does
+impl From<&str> for AnchorComponent {
+  fn from(s: &str) -> Self {
+    Self(s.chars().flat_map(|c| (c as u32).to_le_bytes()).collect())
+  }
+}
+
+impl From<String> for AnchorComponent {
+  fn from(s: String) -> Self {
+    Self::from(s.as_str())
+  }
+}
+
+impl TryFrom<&AnchorComponent> for String {
+  type Error = WasmError;
+  fn try_from(component: &AnchorComponent) -> Result<Self, Self::Error> {
+    if component.0.len() % 4 != 0 {
+      return Err(wasm_error!(WasmErrorInner::Guest("Component is not a string".to_string())));
+    }
+    component.0
+      .chunks(4)
+      .map(|bytes| char::from_u32(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])))
+      .collect::<Option<String>>()
+      .ok_or(wasm_error!(WasmErrorInner::Guest("Component is not a string".to_string())))
+  }
+}
+
+impl AnchorComponent {
+  pub fn as_bytes(&self) -> &[u8] {
+    &self.0
+  }
+
+  /// Component a link tag made by `AnchorPath::make_tag()` was made from
+  pub fn from_tag(tag: &LinkTag) -> Option<Self> {
+    Self::try_from(SerializedBytes::from(UnsafeBytes::from(tag.0.clone()))).ok()
+  }
+}
+
+
+/// warning: mirror of hdk::hash_path::path::Path, must serialize the same way
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize, SerializedBytes)]
+#[repr(transparent)]
+pub struct AnchorPath(Vec<AnchorComponent>);
+
+impl From<Vec<AnchorComponent>> for AnchorPath {
+  fn from(components: Vec<AnchorComponent>) -> Self {
+    Self(components)
+  }
+}
+
+/// A single component path, e.g. the root anchor of a piece kind
+impl From<&str> for AnchorPath {
+  fn from(s: &str) -> Self {
+    Self(vec![AnchorComponent::from(s)])
+  }
+}
+
+impl AnchorPath {
+  pub fn components(&self) -> &[AnchorComponent] {
+    &self.0
+  }
+
+  pub fn leaf(&self) -> Option<&AnchorComponent> {
+    self.0.last()
+  }
+
+  pub fn parent(&self) -> Option<Self> {
+    if self.0.len() <= 1 {
+      return None;
+    }
+    Some(Self(self.0[..self.0.len() - 1].to_vec()))
+  }
+
+  pub fn child(&self, component: AnchorComponent) -> Self {
+    let mut components = self.0.clone();
+    components.push(component);
+    Self(components)
+  }
+
+  pub fn path_entry_hash(&self) -> ExternResult<EntryHash> {
+    let bytes = SerializedBytes::try_from(self).map_err(|e| wasm_error!(e))?;
+    hash_entry(Entry::App(AppEntryBytes(bytes)))
+  }
+
+  /// Tag of the link to this path from its parent, the serialized leaf component
+  pub fn make_tag(&self) -> ExternResult<LinkTag> {
+    let Some(leaf) = self.leaf()
+      else { return Ok(LinkTag::new(Vec::new())) };
+    let bytes = SerializedBytes::try_from(leaf).map_err(|e| wasm_error!(e))?;
+    Ok(LinkTag::new(Vec::<u8>::from(UnsafeBytes::from(bytes))))
+  }
+
+  /// Whether this is the link `ensure()` creates to this path:
+  /// from its parent, or from the root hash for a single component path
+  pub fn is_ensure_link(&self, base: &AnyLinkableHash, target: &AnyLinkableHash, tag: &LinkTag) -> ExternResult<bool> {
+    let parent_hash: AnyLinkableHash = match self.parent() {
+      Some(parent) => parent.path_entry_hash()?.into(),
+      None => anchor_root_hash()?,
+    };
+    Ok(*base == parent_hash
+      && *target == AnyLinkableHash::from(self.path_entry_hash()?)
+      && *tag == self.make_tag()?)
+  }
+}
+
+
+/// warning: mirror of hdk::hash_path::path::root_hash(), base of the links to single component paths
+pub fn anchor_root_hash() -> ExternResult<AnyLinkableHash> {
+  let bytes = AppEntryBytes::try_from(SerializedBytes::from(UnsafeBytes::from(ROOT.to_vec())))
+    .map_err(|e| wasm_error!(WasmErrorInner::Guest(e.to_string())))?;
+  Ok(hash_entry(Entry::App(bytes))?.into())
+}
+
+
+#[cfg(test)]
+mod tests {
+  use hdk::hash_path::path::{Component, Path};
+
+  use super::*;
+
+  fn hdk_path(components: &[&str]) -> Path {
+    Path::from(components.iter().map(|c| Component::from(*c)).collect::<Vec<Component>>())
+  }
+
+  fn anchor_path(components: &[&str]) -> AnchorPath {
+    AnchorPath::from(components.iter().map(|c| AnchorComponent::from(*c)).collect::<Vec<AnchorComponent>>())
+  }
+
+  #[test]
+  fn anchor_path_serializes_like_the_hdk_path() {
+    for components in [vec!["templates"], vec!["templates", "f"], vec!["name_index", "Space", "fo", "été"]] {
+      let expected = SerializedBytes::try_from(hdk_path(&components)).unwrap();
+      let actual = SerializedBytes::try_from(anchor_path(&components)).unwrap();
+      assert_eq!(expected.bytes(), actual.bytes());
+      assert_eq!(hdk_path(&components).make_tag().unwrap(), anchor_path(&components).make_tag().unwrap());
+    }
+  }
+
+  #[test]
+  fn component_roundtrips_through_its_tag() {
+    let path = anchor_path(&["tags", "forêt"]);
+    let leaf = AnchorComponent::from_tag(&path.make_tag().unwrap()).unwrap();
+    assert_eq!(String::try_from(&leaf).unwrap(), "forêt");
+    assert!(AnchorComponent::from_tag(&LinkTag::new("forêt".as_bytes().to_vec())).is_none());
+  }
+}
@@ -0,0 +1,84 @@
+use hdi::prelude::*;
+
+use crate::*;
+
+
+/// Link types of a zome's listing links, see `link_to_shard()`
+pub trait ListingLinkType: Copy + TryInto<ScopedLinkType, Error = WasmError> + LinkTypeFilterExt {
+  /// Link type of the listing links created on a root anchor before sharding
+  fn legacy() -> Self;
+}
+
+
+/// Number of leading characters of a name indexed as path components
+pub const NAME_INDEX_DEPTH: usize = 3;
+
+/// Shard used for names that do not start with an alphanumeric character
+pub const DEFAULT_SHARD: &str = "_";
+
+
+/// Shard key of a name: its lowercased first character
+pub fn name_shard(name: &str) -> String {
+  match name.trim().chars().next() {
+    Some(c) if c.is_alphanumeric() => c.to_lowercase().collect(),
+    _ => DEFAULT_SHARD.to_string(),
+  }
+}
+
+
+/// Path of the anchor holding the entries of a root whose name falls in the given shard
+pub fn shard_path(root_path: &str, shard: &str) -> AnchorPath {
+  AnchorPath::from(vec![AnchorComponent::from(root_path), AnchorComponent::from(shard)])
+}
+
+
+/// Form of a name used for case-insensitive lookups
+pub fn normalize_name(name: &str) -> String {
+  name.trim().to_lowercase()
+}
+
+
+/// Path of the name index node of a normalized name prefix.
+/// Each level below the type name is a longer prefix, up to NAME_INDEX_DEPTH characters,
+/// so the path of a node can be rebuilt from its last component alone.
+pub fn name_index_path(root_path: &str, type_name: &str, normalized_prefix: &str) -> AnchorPath {
+  let mut components = vec![AnchorComponent::from(root_path), AnchorComponent::from(type_name)];
+  let mut prefix = String::new();
+  for c in normalized_prefix.chars().take(NAME_INDEX_DEPTH) {
+    prefix.push(c);
+    components.push(AnchorComponent::from(prefix.as_str()));
+  }
+  AnchorPath::from(components)
+}
+
+
+/// Paths of the name index of a type that end with the given component, as a link tag made by `ensure()` names it
+pub fn name_index_paths_ending_with(root_path: &str, type_name: &str, leaf: &str) -> Vec<AnchorPath> {
+  let mut paths = Vec::new();
+  if leaf == root_path {
+    paths.push(AnchorPath::from(root_path));
+  }
+  if leaf == type_name {
+    paths.push(name_index_path(root_path, type_name, ""));
+  }
+  if (1..=NAME_INDEX_DEPTH).contains(&leaf.chars().count()) {
+    paths.push(name_index_path(root_path, type_name, leaf));
+  }
+  paths
+}
+
+
+/// Path of the anchor of a tag
+pub fn tag_path(root_path: &str, tag: &str) -> AnchorPath {
+  AnchorPath::from(vec![AnchorComponent::from(root_path), AnchorComponent::from(normalize_name(tag))])
+}
+
+
+/// Paths of the tag anchors that end with the given component, as a link tag made by `ensure()` names it
+pub fn tag_paths_ending_with(root_path: &str, leaf: &str) -> Vec<AnchorPath> {
+  let mut paths = vec![tag_path(root_path, leaf)];
+  if leaf == root_path {
+    paths.push(AnchorPath::from(root_path));
+  }
+  paths
+}
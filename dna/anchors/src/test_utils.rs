@@ -0,0 +1,116 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use hdi::prelude::*;
+use sha2::{Digest, Sha256};
+
+
+/// Number of entry and link types of the zome described by `zome_info()`
+const MAX_TYPES: u8 = 32;
+
+
+thread_local! {
+  static ENTRIES: RefCell<HashMap<EntryHash, Entry>> = RefCell::new(HashMap::new());
+  static ACTIONS: RefCell<HashMap<ActionHash, Action>> = RefCell::new(HashMap::new());
+}
+
+
+/// HDI for native unit tests of validation callbacks.
+/// Hashes like the host and serves the entries and actions added on the current thread.
+pub struct TestHdi;
+
+impl TestHdi {
+  /// Install a TestHdi with no entries nor actions for the current thread
+  pub fn install() {
+    ENTRIES.with(|entries| entries.borrow_mut().clear());
+    ACTIONS.with(|actions| actions.borrow_mut().clear());
+    set_hdi(TestHdi);
+  }
+}
+
+
+/// Store an entry for the TestHdi and return its hash
+pub fn add_test_entry<T>(typed: T) -> EntryHash
+  where Entry: TryFrom<T, Error = WasmError>
+{
+  let entry = Entry::try_from(typed).expect("entry must serialize");
+  let eh = EntryHash::with_data_sync(&entry);
+  ENTRIES.with(|entries| entries.borrow_mut().insert(eh.clone(), entry));
+  eh
+}
+
+
+/// Store an action for the TestHdi and return its hash
+pub fn add_test_action(action: Action) -> ActionHash {
+  let ah = ActionHash::with_data_sync(&action);
+  ACTIONS.with(|actions| actions.borrow_mut().insert(ah.clone(), action));
+  ah
+}
+
+
+fn not_found<T>(what: String) -> ExternResult<T> {
+  Err(wasm_error!(WasmErrorInner::Host(format!("{} not found", what))))
+}
+
+
+impl HdiT for TestHdi {
+  fn verify_signature(&self, _: VerifySignature) -> ExternResult<bool> {
+    Ok(true)
+  }
+  fn hash(&self, hash_input: HashInput) -> ExternResult<HashOutput> {
+    match hash_input {
+      HashInput::Entry(entry) => Ok(HashOutput::Entry(EntryHash::with_data_sync(&entry))),
+      HashInput::Action(action) => Ok(HashOutput::Action(ActionHash::with_data_sync(&action))),
+      HashInput::Sha256(data) => {
+        let digest: [u8; 32] = Sha256::digest(data).into();
+        Ok(HashOutput::Sha256(digest.into()))
+      },
+      _ => not_found("hash function".to_string()),
+    }
+  }
+  fn must_get_entry(&self, input: MustGetEntryInput) -> ExternResult<EntryHashed> {
+    let Some(entry) = ENTRIES.with(|entries| entries.borrow().get(&input.0).cloned())
+      else { return not_found(format!("entry {}", input.0)) };
+    Ok(EntryHashed::with_pre_hashed(entry, input.0))
+  }
+  fn must_get_action(&self, input: MustGetActionInput) -> ExternResult<SignedActionHashed> {
+    let Some(action) = ACTIONS.with(|actions| actions.borrow().get(&input.0).cloned())
+      else { return not_found(format!("action {}", input.0)) };
+    let hashed = ActionHashed::with_pre_hashed(action, input.0);
+    Ok(SignedActionHashed::with_presigned(hashed, Signature([0; 64])))
+  }
+  fn must_get_valid_record(&self, input: MustGetValidRecordInput) -> ExternResult<Record> {
+    not_found(format!("record {}", input.0))
+  }
+  /// Every action added for the author, latest first, whatever the chain filter
+  fn must_get_agent_activity(&self, input: MustGetAgentActivityInput) -> ExternResult<Vec<RegisterAgentActivity>> {
+    let mut activity: Vec<RegisterAgentActivity> = ACTIONS.with(|actions| actions.borrow().iter()
+      .filter(|(_, action)| *action.author() == input.author)
+      .map(|(ah, action)| RegisterAgentActivity {
+        action: SignedActionHashed::with_presigned(ActionHashed::with_pre_hashed(action.clone(), ah.clone()), Signature([0; 64])),
+        cached_entry: None,
+      })
+      .collect());
+    activity.sort_by_key(|item| std::cmp::Reverse(item.action.action().action_seq()));
+    Ok(activity)
+  }
+  fn dna_info(&self, _: ()) -> ExternResult<DnaInfo> {
+    not_found("dna info".to_string())
+  }
+  /// A single zome at index 0, with enough entry and link types for any zome of this DNA
+  fn zome_info(&self, _: ()) -> ExternResult<ZomeInfo> {
+    let zome_types = ScopedZomeTypesSet {
+      entries: ScopedZomeTypes(vec![(ZomeIndex(0), (0..MAX_TYPES).map(EntryDefIndex).collect())]),
+      links: ScopedZomeTypes(vec![(ZomeIndex(0), (0..MAX_TYPES).map(LinkType).collect())]),
+    };
+    Ok(ZomeInfo::new(ZomeName::from("test"), ZomeIndex(0), SerializedBytes::default(), EntryDefs(Vec::new()), Vec::new(), zome_types))
+  }
+  fn trace(&self, _: TraceMsg) -> ExternResult<()> {
+    Ok(())
+  }
+  fn x_salsa20_poly1305_decrypt(&self, _: XSalsa20Poly1305Decrypt) -> ExternResult<Option<XSalsa20Poly1305Data>> {
+    Ok(None)
+  }
+  fn x_25519_x_salsa20_poly1305_decrypt(&self, _: X25519XSalsa20Poly1305Decrypt) -> ExternResult<Option<XSalsa20Poly1305Data>> {
+    Ok(None)
+  }
+}
@@ -0,0 +1,37 @@
+use hdi::prelude::*;
+
+
+/// Where a sub-space is attached on the surface of its parent space.
+/// Carried in the tag of the links between the two spaces.
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SpaceAttachment {
+  Whole,         // the whole parent surface
+  Zone(String),  // name of a zone of the parent
+  Point {x: f64, y: f64},
+}
+
+impl SpaceAttachment {
+  pub fn to_tag(&self) -> ExternResult<LinkTag> {
+    let sb = SerializedBytes::try_from(self.clone()).map_err(|e| wasm_error!(e))?;
+    Ok(LinkTag::new(sb.bytes().to_vec()))
+  }
+
+  pub fn from_tag(tag: LinkTag) -> Option<Self> {
+    SpaceAttachment::try_from(SerializedBytes::from(UnsafeBytes::from(tag.into_inner()))).ok()
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn attachment_roundtrips_through_its_tag() {
+    for attachment in [SpaceAttachment::Whole, SpaceAttachment::Zone("Kitchen".to_string()), SpaceAttachment::Point {x: 1.5, y: -2.0}] {
+      assert_eq!(SpaceAttachment::from_tag(attachment.to_tag().unwrap()), Some(attachment));
+    }
+    assert_eq!(SpaceAttachment::from_tag(LinkTag::new(Vec::new())), None);
+  }
+}
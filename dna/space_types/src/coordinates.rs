@@ -0,0 +1,10 @@
+use hdi::prelude::*;
+
+
+/// How the value of a Here placed on a Space is interpreted
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum CoordinateSystem {
+  Cartesian, // {"x": number, "y": number} in surface coordinates
+  Geo,       // {"lat": degrees, "lon": degrees}
+}
@@ -0,0 +1,12 @@
+//! Types of the fields of a Space, shared by the playset and where zomes.
+//!
+//! The where zome decodes Spaces from the playset zome and can't depend on
+//! playset_integrity: it would link its `validate` and `entry_defs` externs twice.
+
+mod attachment;
+mod coordinates;
+mod zone;
+
+pub use attachment::*;
+pub use coordinates::*;
+pub use zone::*;
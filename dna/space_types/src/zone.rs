@@ -0,0 +1,70 @@
+use hdi::prelude::*;
+
+
+/// Named region of a Space's surface
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Zone {
+  pub name: String,
+  pub shape: ZoneShape,
+}
+
+
+/// Zone outline in surface coordinates
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum ZoneShape {
+  Rect {x: f64, y: f64, width: f64, height: f64},
+  Polygon(Vec<(f64, f64)>),
+}
+
+impl ZoneShape {
+  /// Check if a position is inside the shape (even-odd rule for polygons)
+  pub fn contains(&self, px: f64, py: f64) -> bool {
+    match self {
+      ZoneShape::Rect {x, y, width, height} => {
+        px >= *x && px <= x + width && py >= *y && py <= y + height
+      },
+      ZoneShape::Polygon(vertices) => {
+        let mut inside = false;
+        let mut j = vertices.len().wrapping_sub(1);
+        for i in 0..vertices.len() {
+          let (xi, yi) = vertices[i];
+          let (xj, yj) = vertices[j];
+          if (yi > py) != (yj > py)
+            && px < (xj - xi) * (py - yi) / (yj - yi) + xi {
+            inside = !inside;
+          }
+          j = i;
+        }
+        inside
+      },
+    }
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rect_contains_its_edges() {
+    let rect = ZoneShape::Rect {x: 10.0, y: 10.0, width: 20.0, height: 5.0};
+    assert!(rect.contains(10.0, 10.0));
+    assert!(rect.contains(30.0, 15.0));
+    assert!(!rect.contains(30.1, 12.0));
+    assert!(!rect.contains(20.0, 9.9));
+  }
+
+  #[test]
+  fn polygon_contains_by_the_even_odd_rule() {
+    // U shape: the notch between its arms is outside
+    let u = ZoneShape::Polygon(vec![(0.0, 0.0), (30.0, 0.0), (30.0, 30.0), (20.0, 30.0), (20.0, 10.0), (10.0, 10.0), (10.0, 30.0), (0.0, 30.0)]);
+    assert!(u.contains(5.0, 20.0));
+    assert!(u.contains(15.0, 5.0));
+    assert!(!u.contains(15.0, 20.0));
+    assert!(!u.contains(35.0, 5.0));
+    assert!(!ZoneShape::Polygon(Vec::new()).contains(0.0, 0.0));
+  }
+}
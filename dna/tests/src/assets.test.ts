@@ -0,0 +1,38 @@
+import {assert, expect, test} from "vitest";
+import {runScenario} from "@holochain/tryorama";
+import {EntryHashB64} from "@holochain/client";
+
+import {callPlayset, setupPlayers, sync} from "./common";
+
+
+interface Asset {name: string, mimeType: string, size: number, hash: string, chunks: EntryHashB64[]}
+
+
+test("Assets are chunked, listed and attached to surfaces", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob] = await setupPlayers(scenario, 2);
+    /* Bigger than one chunk of 256 KiB */
+    const data = Uint8Array.from({length: 300 * 1024}, (_, i) => i % 251);
+    const assetEh = await callPlayset<EntryHashB64>(alice, "create_asset", {name: "map.png", mimeType: "image/png", data});
+    const templateEh = await callPlayset<EntryHashB64>(alice, "create_template", {name: "Map", surface: "{}"});
+    await callPlayset(alice, "attach_asset", {pieceEh: templateEh, assetEh});
+    await sync([alice, bob]);
+
+    const asset = await callPlayset<{manifest: Asset, data: Uint8Array}>(bob, "get_asset", assetEh);
+    assert.equal(asset.manifest.chunks.length, 2);
+    assert.deepEqual(Array.from(asset.data), Array.from(data));
+    const all = await callPlayset<{hash: EntryHashB64, manifest: Asset}[]>(bob, "get_all_assets", null);
+    assert.deepEqual(all.map((a) => a.hash), [assetEh]);
+    assert.deepEqual(await callPlayset<EntryHashB64[]>(bob, "get_surface_assets", templateEh), [assetEh]);
+  });
+});
+
+
+test("Only templates and spaces display assets", async () => {
+  await runScenario(async (scenario) => {
+    const [alice] = await setupPlayers(scenario, 1);
+    const assetEh = await callPlayset<EntryHashB64>(alice, "create_asset", {name: "a.png", mimeType: "image/png", data: new Uint8Array([1, 2, 3])});
+    const markerEh = await callPlayset<EntryHashB64>(alice, "create_svg_marker", {name: "Pin", value: "<svg/>"});
+    await expect(callPlayset(alice, "attach_asset", {pieceEh: markerEh, assetEh})).rejects.toThrow(/Template or a Space/);
+  });
+});
@@ -0,0 +1,45 @@
+import {assert, expect, test} from "vitest";
+import {runScenario} from "@holochain/tryorama";
+import {ActionHashB64, decodeHashFromBase64} from "@holochain/client";
+import type {HereOutput, HrlWithContext} from "../../../webcomponents/src/bindings/where.types";
+
+import {addHere, callWhere, createNextSession, createSpace, setupPlayers, sync} from "./common";
+
+
+test("Attachments follow the Here's updates and find it back from their HRL", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob] = await setupPlayers(scenario, 2);
+    const spaceEh = await createSpace(alice, "Office");
+    const [, index] = await createNextSession(alice, spaceEh, "Monday");
+    const hereAh = await addHere(alice, spaceEh, index, 1, 1);
+    await sync([alice, bob]);
+
+    /* Any record of any DNA, here the space itself */
+    const hrl = [alice.cells[0].cell_id[0], decodeHashFromBase64(spaceEh)];
+    const hrlWithContext = {hrl, context: JSON.stringify({page: 2})};
+    await callWhere(bob, "add_here_attachment", {hereLinkAh: hereAh, hrlWithContext});
+    await callWhere(bob, "add_here_attachment", {hereLinkAh: hereAh, hrlWithContext});
+    await expect(callWhere(bob, "add_here_attachment", {hereLinkAh: hereAh, hrlWithContext: {hrl, context: "page 2"}}))
+      .rejects.toThrow(/context must be JSON/);
+    await sync([alice, bob]);
+
+    /* Moving the Here keeps its attachments */
+    const movedAh = await callWhere<ActionHashB64>(alice, "update_here", {oldHereAh: hereAh, newHere: {spaceEh, sessionIndex: index, value: JSON.stringify({x: 5, y: 5}), meta: {}}});
+    await sync([alice, bob]);
+    const attachments = await callWhere<HrlWithContext[]>(alice, "get_here_attachments", movedAh);
+    assert.equal(attachments.length, 1);
+    assert.equal(attachments[0].context, hrlWithContext.context);
+    const heres = await callWhere<HereOutput[]>(alice, "get_heres_attached_to", hrl);
+    assert.equal(heres.length, 1);
+    assert.equal(heres[0].linkAh, movedAh);
+
+    /* Only the attachments we made can be removed */
+    await callWhere(alice, "remove_here_attachment", {hereLinkAh: movedAh, hrlWithContext});
+    await sync([alice, bob]);
+    assert.equal((await callWhere<HrlWithContext[]>(bob, "get_here_attachments", movedAh)).length, 1);
+    await callWhere(bob, "remove_here_attachment", {hereLinkAh: movedAh, hrlWithContext});
+    await sync([alice, bob]);
+    assert.equal((await callWhere<HrlWithContext[]>(alice, "get_here_attachments", movedAh)).length, 0);
+    assert.equal((await callWhere<HereOutput[]>(alice, "get_heres_attached_to", hrl)).length, 0);
+  });
+});
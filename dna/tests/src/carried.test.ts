@@ -0,0 +1,66 @@
+import {assert, expect, test} from "vitest";
+import {runScenario} from "@holochain/tryorama";
+import {AgentPubKeyB64, encodeHashToBase64} from "@holochain/client";
+import type {HereOutput} from "../../../webcomponents/src/bindings/where.types";
+
+import {addHere, callWhere, createNextSession, createSpace, setupPlayers, sync} from "./common";
+
+
+interface CarriedHereOutput {
+  carriedAh: string,
+  author: AgentPubKeyB64,
+  here: {value: string, sessionEh: string},
+}
+
+
+test("Latest Heres of the previous session are carried to their authors", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob] = await setupPlayers(scenario, 2);
+    const aliceKey = encodeHashToBase64(alice.agentPubKey);
+    const bobKey = encodeHashToBase64(bob.agentPubKey);
+    const spaceEh = await createSpace(alice, "Office");
+    const [, firstIndex] = await createNextSession(alice, spaceEh, "Monday");
+    await sync([alice, bob]);
+    await addHere(alice, spaceEh, firstIndex, 1, 1);
+    await addHere(alice, spaceEh, firstIndex, 2, 2);
+    await addHere(bob, spaceEh, firstIndex, 3, 3);
+    await sync([alice, bob]);
+
+    const [sessionEh, index] = await createNextSession(alice, spaceEh, "Tuesday", {carryForward: true});
+    assert.equal(index, firstIndex + 1);
+    await sync([alice, bob]);
+
+    /* One carried Here per agent, the latest one */
+    const carried = await callWhere<CarriedHereOutput[]>(bob, "get_carried_heres", sessionEh);
+    assert.equal(carried.length, 2);
+    const alices = carried.find((c) => c.author == aliceKey);
+    const bobs = carried.find((c) => c.author == bobKey);
+    assert.deepEqual(JSON.parse(alices.here.value), {x: 2, y: 2});
+    assert.deepEqual(JSON.parse(bobs.here.value), {x: 3, y: 3});
+
+    /* Only its author can confirm a carried Here */
+    await expect(callWhere(bob, "confirm_carried_here", alices.carriedAh)).rejects.toThrow(/belongs to another agent/);
+    await callWhere(bob, "confirm_carried_here", bobs.carriedAh);
+    await callWhere(alice, "discard_carried_here", alices.carriedAh);
+    await sync([alice, bob]);
+
+    const heres = await callWhere<HereOutput[]>(alice, "get_heres", sessionEh);
+    assert.equal(heres.length, 1);
+    assert.equal(heres[0].author, bobKey);
+    assert.equal(heres[0].entry.sessionEh, sessionEh);
+    assert.deepEqual(JSON.parse(heres[0].entry.value), {x: 3, y: 3});
+    assert.equal((await callWhere<CarriedHereOutput[]>(alice, "get_carried_heres", sessionEh)).length, 0);
+  });
+});
+
+
+test("Heres are not carried unless asked", async () => {
+  await runScenario(async (scenario) => {
+    const [alice] = await setupPlayers(scenario, 1);
+    const spaceEh = await createSpace(alice, "Office");
+    const [, firstIndex] = await createNextSession(alice, spaceEh, "Monday");
+    await addHere(alice, spaceEh, firstIndex, 1, 1);
+    const [sessionEh] = await createNextSession(alice, spaceEh, "Tuesday");
+    assert.equal((await callWhere<CarriedHereOutput[]>(alice, "get_carried_heres", sessionEh)).length, 0);
+  });
+});
@@ -0,0 +1,92 @@
+import {assert, expect, test} from "vitest";
+import {runScenario} from "@holochain/tryorama";
+import {ActionHashB64, encodeHashToBase64} from "@holochain/client";
+
+import {addHere, callWhere, createNextSession, createSpace, setupPlayers, sync} from "./common";
+
+
+interface CommentOutput {
+  commentAh: ActionHashB64,
+  comment: {hereLinkAh: ActionHashB64, text: string, maybeReplyTo?: ActionHashB64},
+  author: string,
+  maybeEditedAt?: number,
+}
+
+
+test("Comments follow the updates of their Here", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob] = await setupPlayers(scenario, 2);
+    const spaceEh = await createSpace(alice, "Board");
+    const [, index] = await createNextSession(alice, spaceEh, "Ideas");
+    await sync([alice, bob]);
+    const hereAh = await addHere(alice, spaceEh, index, 1, 1);
+    await sync([alice, bob]);
+
+    const question = await callWhere<CommentOutput>(bob, "add_here_comment", {hereLinkAh: hereAh, text: "Why here?"});
+    await sync([alice, bob]);
+    await callWhere<CommentOutput>(alice, "add_here_comment", {hereLinkAh: hereAh, text: "Closer to the door", maybeReplyTo: question.commentAh});
+
+    const newHere = {spaceEh, sessionIndex: index, value: JSON.stringify({x: 2, y: 2}), meta: {}};
+    const updatedAh = await callWhere<ActionHashB64>(alice, "update_here", {oldHereAh: hereAh, newHere});
+    await sync([alice, bob]);
+
+    /* Same thread from any version of the Here */
+    const comments = await callWhere<CommentOutput[]>(bob, "get_here_comments", updatedAh);
+    assert.deepEqual(comments.map((c) => c.comment.text), ["Why here?", "Closer to the door"]);
+    assert.equal(comments[0].author, encodeHashToBase64(bob.agentPubKey));
+    assert.equal(comments[1].comment.maybeReplyTo, question.commentAh);
+    assert.deepEqual(await callWhere<CommentOutput[]>(bob, "get_here_comments", hereAh), comments);
+
+    /* Comments made after the update land in the same thread */
+    await callWhere(bob, "add_here_comment", {hereLinkAh: updatedAh, text: "Fair enough"});
+    await sync([alice, bob]);
+    assert.equal((await callWhere<CommentOutput[]>(alice, "get_here_comments", hereAh)).length, 3);
+  });
+});
+
+
+test("A reply must be to a comment on the same Here", async () => {
+  await runScenario(async (scenario) => {
+    const [alice] = await setupPlayers(scenario, 1);
+    const spaceEh = await createSpace(alice, "Board");
+    const [, index] = await createNextSession(alice, spaceEh, "Ideas");
+    const firstAh = await addHere(alice, spaceEh, index, 1, 1);
+    const secondAh = await addHere(alice, spaceEh, index, 2, 2);
+    const comment = await callWhere<CommentOutput>(alice, "add_here_comment", {hereLinkAh: firstAh, text: "First"});
+
+    await expect(callWhere(alice, "add_here_comment", {hereLinkAh: secondAh, text: "Elsewhere", maybeReplyTo: comment.commentAh}))
+      .rejects.toThrow(/same Here/);
+    await expect(callWhere(alice, "add_here_comment", {hereLinkAh: firstAh, text: "Not a comment", maybeReplyTo: firstAh}))
+      .rejects.toThrow(/must be to a comment/);
+    await expect(callWhere(alice, "add_here_comment", {hereLinkAh: firstAh, text: "  "}))
+      .rejects.toThrow(/Comment is empty/);
+  });
+});
+
+
+test("Only its author can edit or delete a comment", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob] = await setupPlayers(scenario, 2);
+    const spaceEh = await createSpace(alice, "Board");
+    const [, index] = await createNextSession(alice, spaceEh, "Ideas");
+    await sync([alice, bob]);
+    const hereAh = await addHere(alice, spaceEh, index, 1, 1);
+    await sync([alice, bob]);
+    const comment = await callWhere<CommentOutput>(bob, "add_here_comment", {hereLinkAh: hereAh, text: "Typo"});
+    await sync([alice, bob]);
+
+    await expect(callWhere(alice, "edit_here_comment", {commentAh: comment.commentAh, text: "Hijacked"}))
+      .rejects.toThrow(/Only the author can edit/);
+    await expect(callWhere(alice, "delete_here_comment", comment.commentAh))
+      .rejects.toThrow(/Only the author can delete/);
+
+    const edited = await callWhere<CommentOutput>(bob, "edit_here_comment", {commentAh: comment.commentAh, text: "Fixed"});
+    assert.equal(edited.commentAh, comment.commentAh);
+    assert.equal(edited.comment.text, "Fixed");
+    assert.isOk(edited.maybeEditedAt);
+
+    await callWhere(bob, "delete_here_comment", comment.commentAh);
+    await sync([alice, bob]);
+    assert.equal((await callWhere<CommentOutput[]>(alice, "get_here_comments", hereAh)).length, 0);
+  });
+});
@@ -0,0 +1,67 @@
+import {fileURLToPath} from "node:url";
+import {ActionHashB64, EntryHashB64} from "@holochain/client";
+import {AppWithOptions, dhtSync, Player, Scenario} from "@holochain/tryorama";
+
+
+/** Built by `npm run build:dna` */
+const WHERE_DNA_PATH = fileURLToPath(new URL("../../../artifacts/where.dna", import.meta.url));
+
+/** Where DNA alone, provisioned at install unlike in the happs */
+const whereApp: AppWithOptions = {
+  appBundleSource: {
+    bundle: {
+      manifest: {
+        manifest_version: "1",
+        name: "where-test",
+        roles: [{
+          name: "rWhere",
+          provisioning: {strategy: "create", deferred: false},
+          dna: {path: WHERE_DNA_PATH},
+        }],
+      },
+      resources: {},
+    },
+  },
+};
+
+
+/** Players sharing a network, each with the Where DNA installed */
+export async function setupPlayers(scenario: Scenario, count: number): Promise<Player[]> {
+  const players = await scenario.addPlayersWithApps(Array(count).fill(whereApp));
+  await scenario.shareAllAgents();
+  return players;
+}
+
+/** Wait until every player holds the same DHT ops */
+export async function sync(players: Player[]): Promise<void> {
+  await dhtSync(players, players[0].cells[0].cell_id[0]);
+}
+
+
+/** */
+export function callWhere<T>(player: Player, fn_name: string, payload: unknown = null): Promise<T> {
+  return player.cells[0].callZome({zome_name: "zWhere", fn_name, payload});
+}
+
+/** */
+export function callPlayset<T>(player: Player, fn_name: string, payload: unknown = null): Promise<T> {
+  return player.cells[0].callZome({zome_name: "zPlayset", fn_name, payload});
+}
+
+
+/** Cartesian space on a new template */
+export async function createSpace(player: Player, name: string): Promise<EntryHashB64> {
+  const surface = JSON.stringify({html: "", size: {x: 1000, y: 1000}});
+  const templateEh = await callPlayset<EntryHashB64>(player, "create_template", {name: `${name} template`, surface});
+  return callPlayset<EntryHashB64>(player, "create_space", {name, origin: templateEh, surface, maybeMarkerPiece: null, meta: {}});
+}
+
+/** Next session of a space, returns its EntryHash and index */
+export function createNextSession(player: Player, spaceEh: EntryHashB64, name: string, options: object = {}): Promise<[EntryHashB64, number]> {
+  return callWhere<[EntryHashB64, number]>(player, "create_next_session", {name, spaceEh, ...options});
+}
+
+/** Here at (x, y), returns the ActionHash of its session link */
+export function addHere(player: Player, spaceEh: EntryHashB64, sessionIndex: number, x: number, y: number): Promise<ActionHashB64> {
+  return callWhere<ActionHashB64>(player, "add_here", {spaceEh, sessionIndex, value: JSON.stringify({x, y}), meta: {}});
+}
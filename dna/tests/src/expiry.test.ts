@@ -0,0 +1,44 @@
+import {assert, test} from "vitest";
+import {runScenario} from "@holochain/tryorama";
+import {ActionHashB64} from "@holochain/client";
+import type {HereOutput} from "../../../webcomponents/src/bindings/where.types";
+
+import {addHere, callWhere, createNextSession, createSpace, setupPlayers, sync} from "./common";
+
+
+interface HeresSinceOutput {
+  created: HereOutput[],
+  deleted: ActionHashB64[],
+  cursor: number,
+}
+
+
+test("Expired Heres are reported as deleted, then deleted by their author", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob] = await setupPlayers(scenario, 2);
+    const spaceEh = await createSpace(alice, "Office");
+    const [sessionEh, index] = await createNextSession(alice, spaceEh, "Monday");
+    await sync([alice, bob]);
+    const since = Date.now() * 1000;
+    const lastingAh = await addHere(alice, spaceEh, index, 1, 1);
+    const expiringAh = await callWhere<ActionHashB64>(alice, "add_here", {spaceEh, sessionIndex: index, value: JSON.stringify({x: 2, y: 2}), meta: {}, maybeTtlSecs: 2});
+    await sync([alice, bob]);
+
+    const before = await callWhere<HeresSinceOutput>(bob, "get_heres_since", {sessionEh, since});
+    assert.sameMembers(before.created.map((here) => here.linkAh), [lastingAh, expiringAh]);
+    assert.equal(before.deleted.length, 0);
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+    const after = await callWhere<HeresSinceOutput>(bob, "get_heres_since", {sessionEh, since: before.cursor});
+    assert.deepEqual(after.deleted, [expiringAh]);
+    assert.equal(after.created.length, 0);
+    const next = await callWhere<HeresSinceOutput>(bob, "get_heres_since", {sessionEh, since: after.cursor});
+    assert.equal(next.deleted.length, 0);
+
+    /* Run the scheduled callback now instead of waiting for it */
+    await callWhere(alice, "delete_expired_heres", null);
+    await sync([alice, bob]);
+    const heres = await callWhere<HereOutput[]>(bob, "get_heres", sessionEh);
+    assert.deepEqual(heres.map((here) => here.linkAh), [lastingAh]);
+  });
+});
@@ -0,0 +1,43 @@
+import {assert, expect, test} from "vitest";
+import {runScenario} from "@holochain/tryorama";
+import {EntryHashB64} from "@holochain/client";
+
+import {callPlayset, setupPlayers} from "./common";
+
+
+interface PageCursor {shard: string, offset: number}
+
+interface TemplatesPage {
+  items: {hash: EntryHashB64, content: {name: string}}[],
+  maybeNextCursor?: PageCursor,
+}
+
+
+test("Pages of templates cover every shard once", async () => {
+  await runScenario(async (scenario) => {
+    const [alice] = await setupPlayers(scenario, 1);
+    const names = ["Forest", "field", "Garden", "harbour", "#1"];
+    for (const name of names) {
+      await callPlayset(alice, "create_template", {name, surface: "{}"});
+    }
+
+    const seen: string[] = [];
+    let maybeCursor: PageCursor | undefined = undefined;
+    do {
+      const page: TemplatesPage = await callPlayset<TemplatesPage>(alice, "get_templates_page", {maybeCursor, limit: 2});
+      assert.isAtMost(page.items.length, 2);
+      seen.push(...page.items.map((item) => item.content.name));
+      maybeCursor = page.maybeNextCursor;
+    } while (maybeCursor);
+    assert.sameMembers(seen, names);
+  });
+});
+
+
+test("A page must hold at least one item", async () => {
+  await runScenario(async (scenario) => {
+    const [alice] = await setupPlayers(scenario, 1);
+    await callPlayset(alice, "create_template", {name: "Forest", surface: "{}"});
+    await expect(callPlayset(alice, "get_templates_page", {limit: 0})).rejects.toThrow(/at least 1/);
+  });
+});
@@ -0,0 +1,44 @@
+import {assert, expect, test} from "vitest";
+import {runScenario} from "@holochain/tryorama";
+import {ActionHashB64, encodeHashToBase64} from "@holochain/client";
+import type {HereOutput} from "../../../webcomponents/src/bindings/where.types";
+
+import {addHere, callWhere, createNextSession, createSpace, setupPlayers, sync} from "./common";
+
+
+interface ReactionOutput {
+  emoji: string,
+  agents: string[],
+}
+
+
+test("Reactions are one per emoji per agent and follow the Here's updates", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob] = await setupPlayers(scenario, 2);
+    const spaceEh = await createSpace(alice, "Office");
+    const [sessionEh, index] = await createNextSession(alice, spaceEh, "Monday");
+    await sync([alice, bob]);
+    const hereAh = await addHere(alice, spaceEh, index, 1, 1);
+    await sync([alice, bob]);
+
+    await callWhere(alice, "react_to_here", {hereLinkAh: hereAh, emoji: "👍"});
+    await callWhere(bob, "react_to_here", {hereLinkAh: hereAh, emoji: "👍"});
+    await callWhere(bob, "react_to_here", {hereLinkAh: hereAh, emoji: "🎉"});
+    await expect(callWhere(bob, "react_to_here", {hereLinkAh: hereAh, emoji: "🎉"})).rejects.toThrow(/Already reacted/);
+    await expect(callWhere(bob, "react_to_here", {hereLinkAh: hereAh, emoji: ""})).rejects.toThrow(/short emoji/);
+    await sync([alice, bob]);
+
+    /* Moving the Here keeps its reactions */
+    const movedAh = await callWhere<ActionHashB64>(alice, "update_here", {oldHereAh: hereAh, newHere: {spaceEh, sessionIndex: index, value: JSON.stringify({x: 5, y: 5}), meta: {}}});
+    await callWhere(bob, "unreact_to_here", {hereLinkAh: movedAh, emoji: "🎉"});
+    await sync([alice, bob]);
+    const reactions = await callWhere<ReactionOutput[]>(alice, "get_here_reactions", movedAh);
+    assert.equal(reactions.length, 1);
+    assert.equal(reactions[0].emoji, "👍");
+    assert.sameMembers(reactions[0].agents, [encodeHashToBase64(alice.agentPubKey), encodeHashToBase64(bob.agentPubKey)]);
+
+    const heres = await callWhere<HereOutput[]>(bob, "get_heres_with_options", {sessionEh, withReactions: true});
+    assert.equal(heres.length, 1);
+    assert.deepEqual(heres[0].maybeReactions, {"👍": 2});
+  });
+});
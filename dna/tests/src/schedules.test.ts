@@ -0,0 +1,74 @@
+import {assert, test} from "vitest";
+import {Player, runScenario} from "@holochain/tryorama";
+import {EntryHashB64} from "@holochain/client";
+
+import {callWhere, createNextSession, createSpace, setupPlayers, sync} from "./common";
+
+
+const DAY_MICROS = 24 * 3600 * 1_000_000;
+
+interface PlacementSession {
+  name: string,
+  index: number,
+  spaceEh: EntryHashB64,
+  maybeOpensAt?: number,
+  maybeClosesAt?: number,
+}
+
+
+/** Daily schedule whose current occurrence started `sinceMicros` ago */
+function createDailySchedule(player: Player, spaceEh: EntryHashB64, sinceMicros: number): Promise<EntryHashB64> {
+  const startsAt = Date.now() * 1000 - sinceMicros;
+  return callWhere<EntryHashB64>(player, "create_session_schedule", {spaceEh, period: "daily", startsAt, name: "Standup"});
+}
+
+/** Run the scheduled callback now instead of waiting for it */
+function runSchedules(player: Player): Promise<unknown> {
+  return callWhere(player, "create_scheduled_sessions", null);
+}
+
+/** */
+async function getSessions(player: Player, spaceEh: EntryHashB64): Promise<PlacementSession[]> {
+  const sessionEhs = await callWhere<EntryHashB64[]>(player, "get_space_sessions", spaceEh);
+  return Promise.all(sessionEhs.map((eh) => callWhere<PlacementSession>(player, "get_session_from_eh", eh)));
+}
+
+
+test("The current occurrence of a schedule is created once, by the agents of its space", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob, carol] = await setupPlayers(scenario, 3);
+    const spaceEh = await createSpace(alice, "Office");
+    await createDailySchedule(alice, spaceEh, 1.5 * DAY_MICROS);
+    await sync([alice, bob, carol]);
+
+    /* Carol never took part in the space */
+    await runSchedules(carol);
+    await sync([alice, bob, carol]);
+    assert.equal((await getSessions(carol, spaceEh)).length, 0);
+
+    /* Only the current occurrence is created, the second one */
+    await runSchedules(alice);
+    await sync([alice, bob, carol]);
+    const sessions = await getSessions(bob, spaceEh);
+    assert.equal(sessions.length, 1);
+    assert.equal(sessions[0].name, "Standup 2");
+    assert.equal(sessions[0].index, 1);
+    assert.equal(sessions[0].maybeClosesAt - sessions[0].maybeOpensAt, DAY_MICROS);
+    await runSchedules(alice);
+    assert.equal((await getSessions(alice, spaceEh)).length, 1);
+  });
+});
+
+
+test("An occurrence is skipped when a session already has its index", async () => {
+  await runScenario(async (scenario) => {
+    const [alice] = await setupPlayers(scenario, 1);
+    const spaceEh = await createSpace(alice, "Office");
+    await createDailySchedule(alice, spaceEh, DAY_MICROS / 2);
+    await createNextSession(alice, spaceEh, "Kickoff");
+    await runSchedules(alice);
+    const sessions = await getSessions(alice, spaceEh);
+    assert.equal(sessions.length, 1);
+    assert.equal(sessions[0].name, "Kickoff");
+  });
+});
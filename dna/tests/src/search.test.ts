@@ -0,0 +1,28 @@
+import {assert, test} from "vitest";
+import {runScenario} from "@holochain/tryorama";
+import {EntryHashB64} from "@holochain/client";
+
+import {callPlayset, setupPlayers, sync} from "./common";
+
+
+interface PieceSearchResult {pieceTypeName: string, hash: EntryHashB64, name: string}
+
+
+test("Search finds pieces by case-insensitive name prefix", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob] = await setupPlayers(scenario, 2);
+    for (const name of ["Forest", "fortress walls", "Fox", "Garden"]) {
+      await callPlayset(alice, "create_template", {name, surface: "{}"});
+    }
+    await callPlayset(alice, "create_svg_marker", {name: "Fort", value: "<svg/>"});
+    await sync([alice, bob]);
+
+    const search = async (query: string, kinds: string[] = []) =>
+      (await callPlayset<PieceSearchResult[]>(bob, "search_pieces", {query, kinds, limit: 10})).map((r) => r.name);
+    /* Shorter and longer than the index depth */
+    assert.sameMembers(await search("FO"), ["Forest", "fortress walls", "Fox", "Fort"]);
+    assert.sameMembers(await search("fort"), ["fortress walls", "Fort"]);
+    assert.sameMembers(await search("fort", ["Template"]), ["fortress walls"]);
+    assert.deepEqual(await search("h"), []);
+  });
+});
@@ -0,0 +1,40 @@
+import {assert, expect, test} from "vitest";
+import {runScenario} from "@holochain/tryorama";
+import {ActionHashB64, AgentPubKeyB64, EntryHashB64} from "@holochain/client";
+
+import {addHere, callWhere, createNextSession, createSpace, setupPlayers, sync} from "./common";
+
+
+interface SessionSnapshot {
+  sessionEh: EntryHashB64,
+  sessionAh: ActionHashB64,
+  heres: {value: string, author: AgentPubKeyB64, linkAh: ActionHashB64}[],
+}
+
+
+test("A snapshot freezes the Heres of a session and closes it", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob] = await setupPlayers(scenario, 2);
+    const spaceEh = await createSpace(alice, "Office");
+    const [sessionEh, index] = await createNextSession(alice, spaceEh, "Monday");
+    await sync([alice, bob]);
+    const bobsAh = await addHere(bob, spaceEh, index, 1, 1);
+    await sync([alice, bob]);
+    assert.isNull(await callWhere(bob, "get_snapshot", sessionEh));
+
+    /* Only the session's creator can take it */
+    await expect(callWhere(bob, "snapshot_session", sessionEh)).rejects.toThrow(/Only the session's creator/);
+    await callWhere<EntryHashB64>(alice, "snapshot_session", sessionEh);
+    await sync([alice, bob]);
+
+    const snapshot = await callWhere<SessionSnapshot>(bob, "get_snapshot", sessionEh);
+    assert.equal(snapshot.sessionEh, sessionEh);
+    assert.deepEqual(snapshot.heres.map((here) => here.linkAh), [bobsAh]);
+    assert.deepEqual(JSON.parse(snapshot.heres[0].value), {x: 1, y: 1});
+    await expect(callWhere(alice, "snapshot_session", sessionEh)).rejects.toThrow(/already has a snapshot/);
+
+    /* Closed sessions refuse new Heres and deletions from this zome */
+    await expect(addHere(bob, spaceEh, index, 2, 2)).rejects.toThrow(/Session is closed/);
+    await expect(callWhere(bob, "delete_here", bobsAh)).rejects.toThrow(/Session is closed/);
+  });
+});
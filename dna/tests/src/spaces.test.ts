@@ -0,0 +1,60 @@
+import {assert, expect, test} from "vitest";
+import {runScenario} from "@holochain/tryorama";
+import {EntryHashB64} from "@holochain/client";
+
+import {addHere, callPlayset, callWhere, createNextSession, createSpace, setupPlayers, sync} from "./common";
+
+
+interface SubSpaceOutput {
+  spaceEh: EntryHashB64,
+  attachment: unknown,
+}
+
+interface SubSpaceRollup {
+  spaceEh: EntryHashB64,
+  attachment: unknown,
+  maybeSessionEh?: EntryHashB64,
+  hereCount: number,
+}
+
+
+test("Sub-spaces are attached to their parent and rolled up", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob] = await setupPlayers(scenario, 2);
+    const building = await createSpace(alice, "Building");
+    const floor = await createSpace(alice, "Floor");
+    const room = await createSpace(alice, "Room");
+    await callPlayset(alice, "attach_sub_space", {parentEh: building, childEh: floor, attachment: "whole"});
+    await callPlayset(alice, "attach_sub_space", {parentEh: floor, childEh: room, attachment: {point: {x: 10, y: 20}}});
+    const [, index] = await createNextSession(alice, room, "Monday");
+    await sync([alice, bob]);
+    await addHere(bob, room, index, 1, 1);
+    await addHere(alice, room, index, 2, 2);
+    await sync([alice, bob]);
+
+    const subSpaces = await callPlayset<SubSpaceOutput[]>(bob, "get_sub_spaces", floor);
+    assert.deepEqual(subSpaces, [{spaceEh: room, attachment: {point: {x: 10, y: 20}}}]);
+    assert.deepEqual(await callPlayset<EntryHashB64[]>(bob, "get_space_ancestors", room), [floor, building]);
+
+    const rollup = await callWhere<SubSpaceRollup[]>(bob, "get_sub_space_rollup", floor);
+    assert.equal(rollup.length, 1);
+    assert.equal(rollup[0].hereCount, 2);
+    assert.equal((await callWhere<SubSpaceRollup[]>(bob, "get_sub_space_rollup", building))[0].hereCount, 0);
+
+    await callPlayset(alice, "detach_sub_space", {parentEh: floor, childEh: room, attachment: {point: {x: 10, y: 20}}});
+    assert.equal((await callPlayset<SubSpaceOutput[]>(alice, "get_sub_spaces", floor)).length, 0);
+  });
+});
+
+
+test("A space can't be its own sub-space nor attach on a zone it doesn't have", async () => {
+  await runScenario(async (scenario) => {
+    const [alice] = await setupPlayers(scenario, 1);
+    const building = await createSpace(alice, "Building");
+    const floor = await createSpace(alice, "Floor");
+    await expect(callPlayset(alice, "attach_sub_space", {parentEh: building, childEh: building, attachment: "whole"}))
+      .rejects.toThrow(/cannot be its own sub-space/);
+    await expect(callPlayset(alice, "attach_sub_space", {parentEh: building, childEh: floor, attachment: {zone: "Attic"}}))
+      .rejects.toThrow(/has no zone 'Attic'/);
+  });
+});
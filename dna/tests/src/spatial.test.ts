@@ -0,0 +1,103 @@
+import {assert, test} from "vitest";
+import {Player, runScenario} from "@holochain/tryorama";
+import {ActionHashB64, EntryHashB64} from "@holochain/client";
+import type {HereOutput} from "../../../webcomponents/src/bindings/where.types";
+
+import {addHere, callPlayset, callWhere, createNextSession, createSpace, setupPlayers, sync} from "./common";
+
+
+/** Geo space on a new template */
+async function createGeoSpace(player: Player, name: string): Promise<EntryHashB64> {
+  const surface = JSON.stringify({html: "", size: {x: 360, y: 180}});
+  const templateEh = await callPlayset<EntryHashB64>(player, "create_template", {name: `${name} template`, surface});
+  return callPlayset<EntryHashB64>(player, "create_space", {name, origin: templateEh, surface, maybeMarkerPiece: null, meta: {}, maybeCoordinateSystem: "geo"});
+}
+
+/** */
+function addGeoHere(player: Player, spaceEh: EntryHashB64, sessionIndex: number, lat: number, lon: number): Promise<ActionHashB64> {
+  return callWhere<ActionHashB64>(player, "add_here", {spaceEh, sessionIndex, value: JSON.stringify({lat, lon}), meta: {}});
+}
+
+/** */
+function getHeresInBox(player: Player, sessionEh: EntryHashB64, min: {x: number, y: number}, max: {x: number, y: number}): Promise<HereOutput[]> {
+  return callWhere<HereOutput[]>(player, "get_heres_in_box", {sessionEh, min, max});
+}
+
+
+test("Geo Heres are found in a box of longitude and latitude", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob] = await setupPlayers(scenario, 2);
+    const spaceEh = await createGeoSpace(alice, "Europe");
+    const [sessionEh, index] = await createNextSession(alice, spaceEh, "Trip");
+    await sync([alice, bob]);
+    await addGeoHere(alice, spaceEh, index, 48.8566, 2.3522);  // Paris
+    await addGeoHere(bob, spaceEh, index, 48.8606, 2.3376);    // Louvre, 1.1 km away
+    await addGeoHere(bob, spaceEh, index, 51.5072, -0.1276);   // London
+    await sync([alice, bob]);
+
+    const paris = await getHeresInBox(alice, sessionEh, {x: 2.3, y: 48.8}, {x: 2.4, y: 48.9});
+    assert.equal(paris.length, 2);
+    const louvre = await getHeresInBox(alice, sessionEh, {x: 2.33, y: 48.855}, {x: 2.345, y: 48.865});
+    assert.equal(louvre.length, 1);
+    assert.deepEqual(JSON.parse(louvre[0].entry.value), {lat: 48.8606, lon: 2.3376});
+    const europe = await getHeresInBox(bob, sessionEh, {x: -10, y: 40}, {x: 10, y: 60});
+    assert.equal(europe.length, 3);
+  });
+});
+
+
+test("A deleted Here leaves the spatial index", async () => {
+  await runScenario(async (scenario) => {
+    const [alice] = await setupPlayers(scenario, 1);
+    const spaceEh = await createGeoSpace(alice, "Europe");
+    const [sessionEh, index] = await createNextSession(alice, spaceEh, "Trip");
+    const linkAh = await addGeoHere(alice, spaceEh, index, 48.8566, 2.3522);
+    assert.equal((await getHeresInBox(alice, sessionEh, {x: 2, y: 48}, {x: 3, y: 49})).length, 1);
+
+    await callWhere(alice, "delete_here", linkAh);
+    assert.equal((await getHeresInBox(alice, sessionEh, {x: 2, y: 48}, {x: 3, y: 49})).length, 0);
+    assert.equal((await callWhere<HereOutput[]>(alice, "get_heres", sessionEh)).length, 0);
+  });
+});
+
+
+test("Box queries are clamped to the surface", async () => {
+  await runScenario(async (scenario) => {
+    const [alice] = await setupPlayers(scenario, 1);
+    const spaceEh = await createSpace(alice, "Office");
+    const [sessionEh, index] = await createNextSession(alice, spaceEh, "Monday");
+    await addHere(alice, spaceEh, index, 10, 10);
+    await addHere(alice, spaceEh, index, 900, 900);
+
+    const all = await getHeresInBox(alice, sessionEh, {x: -1e300, y: -1e300}, {x: 1e300, y: 1e300});
+    assert.equal(all.length, 2);
+    const corner = await getHeresInBox(alice, sessionEh, {x: -1e12, y: -1e12}, {x: 100, y: 100});
+    assert.equal(corner.length, 1);
+    assert.equal((await getHeresInBox(alice, sessionEh, {x: 2000, y: 0}, {x: 3000, y: 1000})).length, 0);
+    assert.equal((await getHeresInBox(alice, sessionEh, {x: 100, y: 100}, {x: 0, y: 0})).length, 0);
+  });
+});
+
+
+test("Nearest Heres beyond the searched rings are found", async () => {
+  await runScenario(async (scenario) => {
+    const [alice] = await setupPlayers(scenario, 1);
+    const spaceEh = await createSpace(alice, "Field");
+    const [sessionEh, index] = await createNextSession(alice, spaceEh, "Monday");
+    await addHere(alice, spaceEh, index, 1020, 10);
+    await addHere(alice, spaceEh, index, 1030, 10);
+    /* In the last ring, and outside the rings but closer */
+    await addHere(alice, spaceEh, index, 5100, 5000);
+    await addHere(alice, spaceEh, index, 1020, 5500);
+
+    const nearest = async (x: number, y: number, count: number) => {
+      const heres = await callWhere<HereOutput[]>(alice, "get_nearest_heres", {sessionEh, center: {x, y}, count});
+      return heres.map((here) => JSON.parse(here.entry.value));
+    };
+    /* The closest Here is in the next cell */
+    assert.deepEqual(await nearest(1023, 10, 1), [{x: 1020, y: 10}]);
+    assert.deepEqual(await nearest(1026, 10, 2), [{x: 1030, y: 10}, {x: 1020, y: 10}]);
+    assert.deepEqual(await nearest(1020, 1000, 3), [{x: 1020, y: 10}, {x: 1030, y: 10}, {x: 1020, y: 5500}]);
+    assert.equal((await nearest(0, 0, 10)).length, 4);
+  });
+});
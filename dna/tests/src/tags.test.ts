@@ -0,0 +1,38 @@
+import {assert, expect, test} from "vitest";
+import {runScenario} from "@holochain/tryorama";
+import {EntryHashB64} from "@holochain/client";
+
+import {callPlayset, createNextSession, createSpace, setupPlayers, sync} from "./common";
+
+
+test("Pieces are found by their normalized tags", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob] = await setupPlayers(scenario, 2);
+    const forestEh = await callPlayset<EntryHashB64>(alice, "create_template", {name: "Forest", surface: "{}"});
+    const lakeEh = await callPlayset<EntryHashB64>(alice, "create_template", {name: "Lake", surface: "{}"});
+    await callPlayset(alice, "tag_piece", {pieceEh: forestEh, tag: " Nature "});
+    await callPlayset(alice, "tag_piece", {pieceEh: lakeEh, tag: "nature"});
+    await callPlayset(alice, "tag_piece", {pieceEh: lakeEh, tag: "Water"});
+    await sync([alice, bob]);
+
+    assert.sameMembers(await callPlayset<EntryHashB64[]>(bob, "get_pieces_by_tag", "NATURE"), [forestEh, lakeEh]);
+    assert.deepEqual(await callPlayset<string[]>(bob, "get_piece_tags", lakeEh), ["nature", "water"]);
+    assert.deepEqual(await callPlayset<string[]>(bob, "get_all_tags", null), ["nature", "water"]);
+
+    await callPlayset(alice, "untag_piece", {pieceEh: forestEh, tag: "nature"});
+    await sync([alice, bob]);
+    assert.deepEqual(await callPlayset<EntryHashB64[]>(bob, "get_pieces_by_tag", "nature"), [lakeEh]);
+  });
+});
+
+
+test("Only pieces can be tagged, with a non-empty tag", async () => {
+  await runScenario(async (scenario) => {
+    const [alice] = await setupPlayers(scenario, 1);
+    const forestEh = await callPlayset<EntryHashB64>(alice, "create_template", {name: "Forest", surface: "{}"});
+    await expect(callPlayset(alice, "tag_piece", {pieceEh: forestEh, tag: " "})).rejects.toThrow(/must not be empty/);
+    const spaceEh = await createSpace(alice, "Board");
+    const [sessionEh] = await createNextSession(alice, spaceEh, "Ideas");
+    await expect(callPlayset(alice, "tag_piece", {pieceEh: sessionEh, tag: "nature"})).rejects.toThrow(/not a piece/);
+  });
+});
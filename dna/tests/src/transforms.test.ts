@@ -0,0 +1,78 @@
+import {assert, expect, test} from "vitest";
+import {runScenario} from "@holochain/tryorama";
+import {encodeHashToBase64} from "@holochain/client";
+import type {HereOutput} from "../../../webcomponents/src/bindings/where.types";
+
+import {addHere, callWhere, createNextSession, createSpace, setupPlayers, sync} from "./common";
+
+
+interface HereTransform {
+  scaleX: number,
+  scaleY: number,
+  translateX: number,
+  translateY: number,
+}
+
+/** Positions of the Heres of a session by author */
+function getPositions(heres: HereOutput[]): Map<string, {x: number, y: number}> {
+  return new Map(heres.map((here) => [here.author, JSON.parse(here.entry.value)]));
+}
+
+
+test("Only the session's creator can migrate its Heres", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob] = await setupPlayers(scenario, 2);
+    const aliceKey = encodeHashToBase64(alice.agentPubKey);
+    const bobKey = encodeHashToBase64(bob.agentPubKey);
+    const spaceEh = await createSpace(alice, "Floor plan");
+    const [sessionEh, index] = await createNextSession(alice, spaceEh, "Seating");
+    await sync([alice, bob]);
+    await addHere(alice, spaceEh, index, 10, 20);
+    await addHere(bob, spaceEh, index, 30, 40);
+    await sync([alice, bob]);
+
+    const double: HereTransform = {scaleX: 2, scaleY: 2, translateX: 0, translateY: 0};
+    await expect(callWhere(bob, "migrate_heres", {sessionEh, transform: double})).rejects.toThrow(/session's creator/);
+    await callWhere(alice, "migrate_heres", {sessionEh, transform: double});
+    await callWhere(alice, "migrate_heres", {sessionEh, transform: {scaleX: 1, scaleY: 1, translateX: 5, translateY: -5}});
+    await sync([alice, bob]);
+
+    /* Transforms compose in order and apply to every agent's Heres */
+    const composed = await callWhere<HereTransform>(bob, "get_session_transform", sessionEh);
+    assert.deepEqual(composed, {scaleX: 2, scaleY: 2, translateX: 5, translateY: -5});
+    const positions = getPositions(await callWhere<HereOutput[]>(bob, "get_heres", sessionEh));
+    assert.deepEqual(positions.get(aliceKey), {x: 25, y: 35});
+    assert.deepEqual(positions.get(bobKey), {x: 65, y: 75});
+  });
+});
+
+
+test("Invalid transforms are rejected", async () => {
+  await runScenario(async (scenario) => {
+    const [alice] = await setupPlayers(scenario, 1);
+    const spaceEh = await createSpace(alice, "Floor plan");
+    const [sessionEh] = await createNextSession(alice, spaceEh, "Seating");
+    const flat: HereTransform = {scaleX: 0, scaleY: 1, translateX: 0, translateY: 0};
+    await expect(callWhere(alice, "migrate_heres", {sessionEh, transform: flat})).rejects.toThrow(/non-zero scales/);
+    assert.isNull(await callWhere<HereTransform | null>(alice, "get_session_transform", sessionEh));
+  });
+});
+
+
+test("Carried Heres keep their entry and follow the previous session's transform", async () => {
+  await runScenario(async (scenario) => {
+    const [alice] = await setupPlayers(scenario, 1);
+    const spaceEh = await createSpace(alice, "Floor plan");
+    const [previousEh, previousIndex] = await createNextSession(alice, spaceEh, "Before");
+    await addHere(alice, spaceEh, previousIndex, 10, 20);
+    await callWhere(alice, "migrate_heres", {sessionEh: previousEh, transform: {scaleX: 1, scaleY: 1, translateX: 100, translateY: 0}});
+    const [sessionEh] = await createNextSession(alice, spaceEh, "After", {carryForward: true});
+
+    const carried = await callWhere<{carriedAh: string, here: {value: string}}[]>(alice, "get_carried_heres", sessionEh);
+    assert.equal(carried.length, 1);
+    assert.deepEqual(JSON.parse(carried[0].here.value), {x: 110, y: 20});
+    await callWhere(alice, "confirm_carried_here", carried[0].carriedAh);
+    const heres = await callWhere<HereOutput[]>(alice, "get_heres", sessionEh);
+    assert.deepEqual(JSON.parse(heres[0].entry.value), {x: 110, y: 20});
+  });
+});
@@ -0,0 +1,47 @@
+import {assert, expect, test} from "vitest";
+import {runScenario} from "@holochain/tryorama";
+import type {HereOutput} from "../../../webcomponents/src/bindings/where.types";
+
+import {addHere, callWhere, createNextSession, createSpace, setupPlayers, sync} from "./common";
+
+
+test("Vote budget limits the live Heres of each agent", async () => {
+  await runScenario(async (scenario) => {
+    const [alice, bob] = await setupPlayers(scenario, 2);
+    const spaceEh = await createSpace(alice, "Ballot");
+    const [sessionEh, index] = await createNextSession(alice, spaceEh, "Round 1", {maybeVoteBudget: 2});
+    await sync([alice, bob]);
+
+    assert.equal(await callWhere<number>(alice, "get_remaining_votes", sessionEh), 2);
+    const firstAh = await addHere(alice, spaceEh, index, 10, 10);
+    await addHere(alice, spaceEh, index, 20, 20);
+    assert.equal(await callWhere<number>(alice, "get_remaining_votes", sessionEh), 0);
+    await expect(addHere(alice, spaceEh, index, 30, 30)).rejects.toThrow(/Vote budget of 2 exhausted/);
+
+    /* Budgets are per agent */
+    assert.equal(await callWhere<number>(bob, "get_remaining_votes", sessionEh), 2);
+    await addHere(bob, spaceEh, index, 40, 40);
+
+    /* Deleting a Here gives its vote back */
+    await callWhere(alice, "delete_here", firstAh);
+    assert.equal(await callWhere<number>(alice, "get_remaining_votes", sessionEh), 1);
+    await addHere(alice, spaceEh, index, 50, 50);
+
+    await sync([alice, bob]);
+    const heres = await callWhere<HereOutput[]>(bob, "get_heres", sessionEh);
+    assert.equal(heres.length, 3);
+  });
+});
+
+
+test("Sessions without a vote budget are not limited", async () => {
+  await runScenario(async (scenario) => {
+    const [alice] = await setupPlayers(scenario, 1);
+    const spaceEh = await createSpace(alice, "Open space");
+    const [sessionEh, index] = await createNextSession(alice, spaceEh, "Free");
+    assert.isNull(await callWhere<number | null>(alice, "get_remaining_votes", sessionEh));
+    for (let i = 0; i < 5; i++) {
+      await addHere(alice, spaceEh, index, i, i);
+    }
+  });
+});
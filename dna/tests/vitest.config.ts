@@ -0,0 +1,8 @@
+import {defineConfig} from "vitest/config";
+
+export default defineConfig({
+  test: {
+    threads: false,
+    testTimeout: 60 * 1000 * 3,
+  },
+});
@@ -4,6 +4,8 @@ use holo_hash::EntryHashB64;
 use zome_utils::*;
 use ludotheque_integrity::*;
 
+use crate::tags::get_playset_tags;
+
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -14,27 +16,24 @@ pub struct ExportPlaysetInput {
 
 #[hdk_extern]
 fn export_playset(ExportPlaysetInput{playset_eh, destination_cell_id}: ExportPlaysetInput) -> ExternResult<Vec<EntryHashB64>> {
-  /* Get Playset */
-  let playset: Playset = get_typed_from_eh(playset_eh.into())?;
+  /* Get Playset and its tags, given to each of its pieces */
+  let playset: Playset = get_typed_from_eh(playset_eh.clone().into())?;
+  let playset_tags = get_playset_tags(playset_eh)?;
   /* Export each template */
   for eh in playset.templates {
-    let entry = get_entry_from_eh(eh.into())?;
-    export_piece_inner("Template", entry, destination_cell_id.clone())?;
+    export_piece_inner("Template", eh, &playset_tags, destination_cell_id.clone())?;
   }
   /* Export each svg marker */
   for eh in playset.svg_markers {
-    let entry = get_entry_from_eh(eh.into())?;
-    export_piece_inner("SvgMarker", entry, destination_cell_id.clone())?;
+    export_piece_inner("SvgMarker", eh, &playset_tags, destination_cell_id.clone())?;
   }
-  /* Export each svg marker */
+  /* Export each emoji group */
   for eh in playset.emoji_groups {
-    let entry = get_entry_from_eh(eh.into())?;
-    export_piece_inner("EmojiGroup", entry, destination_cell_id.clone())?;
+    export_piece_inner("EmojiGroup", eh, &playset_tags, destination_cell_id.clone())?;
   }
   /* Export each space */
   for spaceEh in playset.spaces.clone() {
-    let space_entry = get_entry_from_eh(spaceEh.into())?;
-    export_piece_inner("Space", space_entry, destination_cell_id.clone())?;
+    export_piece_inner("Space", spaceEh, &playset_tags, destination_cell_id.clone())?;
   }
   /* Done */
   Ok(playset.spaces)
@@ -46,14 +45,37 @@ fn export_playset(ExportPlaysetInput{playset_eh, destination_cell_id}: ExportPla
 pub struct ImportPieceInput {
   pub piece_type_name: String,
   pub piece_entry: Entry,
+  #[serde(default)]
+  pub tags: Vec<String>,
+  #[serde(default)]
+  pub assets: Vec<EntryHashB64>,
 }
 
-///
-fn export_piece_inner(entry_type_name: &str, piece_entry: Entry, cell_id: CellId) -> ExternResult<()> {
+/// warning: duplicate struct in ludotheque zome
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportAssetInput {
+  pub cell_id: CellId,
+  pub asset_eh: EntryHashB64,
+}
+
+/// Export a piece of this cell's playset zome with its tags, the extra tags, and the assets of its surface
+fn export_piece_inner(entry_type_name: &str, piece_eh: EntryHashB64, extra_tags: &[String], cell_id: CellId) -> ExternResult<()> {
   debug!("export_piece_inner() called: {}", entry_type_name);
+  let piece_entry = get_entry_from_eh(piece_eh.clone().into())?;
+  let mut tags: Vec<String> = call_local_playset_zome("get_piece_tags", piece_eh.clone())?;
+  tags.extend(extra_tags.iter().cloned());
+  tags.sort();
+  tags.dedup();
+  let assets: Vec<EntryHashB64> = call_local_playset_zome("get_surface_assets", piece_eh)?;
+  for asset_eh in assets.clone() {
+    let _: () = call_local_playset_zome("export_asset", ExportAssetInput {cell_id: cell_id.clone(), asset_eh})?;
+  }
   let input = ImportPieceInput {
     piece_type_name: entry_type_name.to_string(),
-    piece_entry
+    piece_entry,
+    tags,
+    assets,
   };
   let res = call(
     CallTargetCell::OtherCell(cell_id),
@@ -65,3 +87,20 @@ fn export_piece_inner(entry_type_name: &str, piece_entry: Entry, cell_id: CellId
   let _: () = decode_response(res)?;
   Ok(())
 }
+
+
+///
+fn call_local_playset_zome<I, O>(fn_name: &str, payload: I) -> ExternResult<O>
+  where
+    I: serde::Serialize + std::fmt::Debug,
+    O: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+  let res = call(
+    CallTargetCell::Local,
+    "zPlayset",
+    fn_name.to_string().into(),
+    None,
+    payload,
+  )?;
+  decode_response(res)
+}
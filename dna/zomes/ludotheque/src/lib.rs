@@ -3,7 +3,13 @@
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 #![allow(unused_attributes)]
+#![allow(clippy::redundant_static_lifetimes)]
+#![allow(clippy::empty_docs)]
+#![allow(clippy::empty_line_after_doc_comments)]
+#![allow(clippy::empty_line_after_outer_attr)]
 
+mod name_index;
+mod tags;
 mod playset;
 mod export_playset;
 
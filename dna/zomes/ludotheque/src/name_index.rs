@@ -0,0 +1,76 @@
+use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+
+use ludotheque_integrity::*;
+use anchors::*;
+
+
+/// Maximum number of playsets returned by a search
+pub const MAX_SEARCH_RESULTS: usize = 100;
+
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaysetSearchResult {
+  pub hash: EntryHashB64,
+  pub name: String,
+}
+
+
+/// Link a new playset from the index node of its name
+pub fn index_playset_name(name: &str, eh: EntryHash) -> ExternResult<ActionHash> {
+  let path = name_index_path(NAME_INDEX_ROOT_PATH, "Playset", &normalize_name(name))
+    .typed(LudothequeLinkType::NameIndex)?;
+  path.ensure()?;
+  create_link(path.path_entry_hash()?, eh, LudothequeLinkType::NameIndex, LinkTag::new(name.as_bytes().to_vec()))
+}
+
+
+/// Case-insensitive search of playsets whose name starts with the query
+#[hdk_extern]
+fn search_playsets(query: String) -> ExternResult<Vec<PlaysetSearchResult>> {
+  let query = normalize_name(&query);
+  let node = name_index_path(NAME_INDEX_ROOT_PATH, "Playset", &query).typed(LudothequeLinkType::NameIndex)?;
+  let results = search_index_node(&node, &query, MAX_SEARCH_RESULTS)?
+    .into_iter()
+    .map(|(eh, name)| PlaysetSearchResult {hash: eh.into(), name})
+    .collect();
+  Ok(results)
+}
+
+
+/// warning: duplicate of search_index_node() in playset zome
+/// Collect matching names under an index node, descending into children
+/// while the query is shorter than the index depth
+fn search_index_node(node: &TypedPath, query: &str, limit: usize) -> ExternResult<Vec<(EntryHash, String)>> {
+  let mut found = Vec::new();
+  if limit == 0 {
+    return Ok(found);
+  }
+  let links = get_links(node.path_entry_hash()?, LudothequeLinkType::NameIndex, None)?;
+  for link in links {
+    let Ok(name) = String::from_utf8(link.tag.into_inner())
+      else {continue};
+    let Some(eh) = link.target.into_entry_hash()
+      else {continue};
+    if !normalize_name(&name).starts_with(query) {
+      continue;
+    }
+    found.push((eh, name));
+    if found.len() >= limit {
+      return Ok(found);
+    }
+  }
+  if node.path.as_ref().len() >= NAME_INDEX_DEPTH + 2 {
+    return Ok(found);
+  }
+  for child in node.children_paths()? {
+    let mut sub = search_index_node(&child, query, limit - found.len())?;
+    found.append(&mut sub);
+    if found.len() >= limit {
+      break;
+    }
+  }
+  Ok(found)
+}
@@ -1,10 +1,12 @@
-use hdk::hash_path::path::TypedPath;
 use hdk::prelude::*;
 use holo_hash::EntryHashB64;
 use zome_utils::*;
 
 use ludotheque_integrity::*;
 
+use anchors::*;
+use crate::name_index::*;
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct PlaysetOutput {
   hash: EntryHashB64,
@@ -12,21 +14,13 @@ pub struct PlaysetOutput {
 }
 
 
-///
-fn get_playset_path() -> TypedPath {
-  Path::from("playsets").typed(LudothequeLinkType::All).unwrap()
-}
-
-
 ///
 #[hdk_extern]
 fn create_playset(input: Playset) -> ExternResult<EntryHashB64> {
   let _hh = create_entry(&LudothequeEntry::Playset(input.clone()))?;
   let eh = hash_entry(input.clone())?;
-  let path = get_playset_path();
-  path.ensure()?;
-  let anchor_eh = path.path_entry_hash()?;
-  create_link(anchor_eh, eh.clone(), LudothequeLinkType::All, LinkTag::from(()))?;
+  link_to_shard(PLAYSETS_ROOT_PATH, &input.name, LudothequeLinkType::Playsets, eh.clone())?;
+  index_playset_name(&input.name, eh.clone())?;
   let eh64: EntryHashB64 = eh.clone().into();
   // let me = agent_info()?.agent_latest_pubkey.into();
   // emit_signal(&SignalPayload::new(None, me, eh64)?;
@@ -46,17 +40,27 @@ fn get_playset(input: EntryHashB64) -> ExternResult<Option<Playset>> {
 
 #[hdk_extern]
 fn get_all_playsets(_: ()) -> ExternResult<Vec<PlaysetOutput>> {
-  let path = get_playset_path();
-  let playsets = get_all_inner(path.path_entry_hash()?)?;
+  let links = get_all_links(PLAYSETS_ROOT_PATH, LudothequeLinkType::Playsets)?;
+  let playsets = get_all_inner(links)?;
   Ok(playsets)
 }
 
-fn get_all_inner(base: EntryHash) -> ExternResult<Vec<PlaysetOutput>> {
-  let entries = get_typed_from_links(base, LudothequeLinkType::All, None)
-    .map_err(|err| wasm_error!(WasmErrorInner::Guest(err.to_string())))?;
+
+#[hdk_extern]
+fn get_playsets_page(input: GetPageInput) -> ExternResult<PageOutput<PlaysetOutput>> {
+  let (links, maybe_next_cursor) = get_page_links(PLAYSETS_ROOT_PATH, LudothequeLinkType::Playsets, &input)?;
+  let items = get_all_inner(links)?;
+  Ok(PageOutput {items, maybe_next_cursor})
+}
+
+fn get_all_inner(links: Vec<Link>) -> ExternResult<Vec<PlaysetOutput>> {
   let mut playsets = vec![];
-  for pair in entries {
-    playsets.push(PlaysetOutput {hash: hash_entry(&pair.0)?.into(), content: pair.0});
+  for link in links {
+    let Some(eh) = link.target.into_entry_hash()
+      else {continue};
+    let Ok(content) = get_typed_from_eh::<Playset>(eh.clone())
+      else {continue};
+    playsets.push(PlaysetOutput {hash: eh.into(), content});
   }
   Ok(playsets)
 }
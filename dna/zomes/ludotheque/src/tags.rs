@@ -0,0 +1,103 @@
+use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use zome_utils::*;
+
+use ludotheque_integrity::*;
+use anchors::*;
+
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagPlaysetInput {
+  pub playset_eh: EntryHashB64,
+  pub tag: String,
+}
+
+
+///
+#[hdk_extern]
+pub fn tag_playset(input: TagPlaysetInput) -> ExternResult<()> {
+  let tag = normalize_name(&input.tag);
+  if tag.is_empty() {
+    return zome_error!("tag must not be empty");
+  }
+  let playset_eh: EntryHash = input.playset_eh.into();
+  if get_playset_tags(playset_eh.clone().into())?.contains(&tag) {
+    return Ok(());
+  }
+  let path = tag_path(TAGS_ROOT_PATH, &tag).typed(LudothequeLinkType::Tagged)?;
+  path.ensure()?;
+  let anchor_eh = path.path_entry_hash()?;
+  let link_tag = LinkTag::new(tag.as_bytes().to_vec());
+  create_link(anchor_eh.clone(), playset_eh.clone(), LudothequeLinkType::Tagged, link_tag.clone())?;
+  create_link(playset_eh, anchor_eh, LudothequeLinkType::PlaysetTags, link_tag)?;
+  Ok(())
+}
+
+
+///
+#[hdk_extern]
+fn untag_playset(input: TagPlaysetInput) -> ExternResult<()> {
+  let tag = normalize_name(&input.tag);
+  let playset_eh: EntryHash = input.playset_eh.into();
+  let anchor_eh = tag_path(TAGS_ROOT_PATH, &tag).path_entry_hash()?;
+  let link_tag = LinkTag::new(tag.as_bytes().to_vec());
+  for link in get_links(anchor_eh.clone(), LudothequeLinkType::Tagged, Some(link_tag.clone()))? {
+    if link.target == AnyLinkableHash::from(playset_eh.clone()) {
+      delete_link(link.create_link_hash)?;
+    }
+  }
+  for link in get_links(playset_eh, LudothequeLinkType::PlaysetTags, Some(link_tag))? {
+    if link.target == AnyLinkableHash::from(anchor_eh.clone()) {
+      delete_link(link.create_link_hash)?;
+    }
+  }
+  Ok(())
+}
+
+
+/// Playsets carrying the given tag
+#[hdk_extern]
+fn get_playsets_by_tag(tag: String) -> ExternResult<Vec<EntryHashB64>> {
+  let tag = normalize_name(&tag);
+  let anchor_eh = tag_path(TAGS_ROOT_PATH, &tag).path_entry_hash()?;
+  let links = get_links(anchor_eh, LudothequeLinkType::Tagged, Some(LinkTag::new(tag.as_bytes().to_vec())))?;
+  let mut playsets: Vec<EntryHashB64> = Vec::new();
+  for link in links {
+    let Some(eh) = link.target.into_entry_hash()
+      else {continue};
+    let eh64: EntryHashB64 = eh.into();
+    if !playsets.contains(&eh64) {
+      playsets.push(eh64);
+    }
+  }
+  Ok(playsets)
+}
+
+
+/// Tags of a playset, sorted
+#[hdk_extern]
+pub fn get_playset_tags(playset_eh: EntryHashB64) -> ExternResult<Vec<String>> {
+  let links = get_links(EntryHash::from(playset_eh), LudothequeLinkType::PlaysetTags, None)?;
+  let mut tags: Vec<String> = links.into_iter()
+    .filter_map(|link| String::from_utf8(link.tag.into_inner()).ok())
+    .collect();
+  tags.sort();
+  tags.dedup();
+  Ok(tags)
+}
+
+
+/// Every tag used on playsets, sorted
+#[hdk_extern]
+fn get_all_playset_tags(_: ()) -> ExternResult<Vec<String>> {
+  let root = Path::from(TAGS_ROOT_PATH).typed(LudothequeLinkType::Tagged)?;
+  let mut tags: Vec<String> = root.children_paths()?
+    .into_iter()
+    .filter_map(|child| child.path.leaf().and_then(|leaf| String::try_from(leaf).ok()))
+    .collect();
+  tags.sort();
+  tags.dedup();
+  Ok(tags)
+}
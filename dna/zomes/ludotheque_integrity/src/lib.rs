@@ -3,9 +3,27 @@
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 #![allow(unused_attributes)]
+#![allow(clippy::redundant_static_lifetimes)]
+#![allow(clippy::empty_docs)]
+#![allow(clippy::empty_line_after_doc_comments)]
+#![allow(clippy::empty_line_after_outer_attr)]
 
 mod playset;
+mod validate;
+
 pub use playset::*;
+pub use validate::*;
+
+
+///-------------------------------------------------------------------------------------------------
+/// Global consts
+///-------------------------------------------------------------------------------------------------
+/// Root path of the playsets anchor
+pub const PLAYSETS_ROOT_PATH: &'static str = "playsets";
+/// Root path of the name-prefix index of playsets
+pub const NAME_INDEX_ROOT_PATH: &'static str = "name_index";
+/// Root path of the tag anchors
+pub const TAGS_ROOT_PATH: &'static str = "tags";
 
 
 ///-------------------------------------------------------------------------------------------------
@@ -30,6 +48,15 @@ pub enum LudothequeEntry {
 #[hdk_link_types]
 #[derive(Serialize, Deserialize)]
 pub enum LudothequeLinkType {
-    All,
+    All, // Legacy listing links, kept readable for playsets created before sharding
+    Playsets,
+    NameIndex,
+    Tagged,      // tag anchor -> playset
+    PlaysetTags, // playset -> tag anchor
 }
 
+impl anchors::ListingLinkType for LudothequeLinkType {
+    fn legacy() -> Self {
+        LudothequeLinkType::All
+    }
+}
@@ -0,0 +1,144 @@
+use hdi::prelude::*;
+use anchors::*;
+
+use crate::*;
+
+
+///
+#[hdk_extern]
+pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+  match op.flattened::<LudothequeEntry, LudothequeLinkType>()? {
+    FlatOp::RegisterCreateLink { link_type, base_address, target_address, tag, .. } => {
+      validate_create_link(link_type, base_address, target_address, tag)
+    },
+    _ => Ok(ValidateCallbackResult::Valid),
+  }
+}
+
+
+/// Playset links must go from the shard anchor of the playset's name to a Playset entry
+fn validate_create_link(link_type: LudothequeLinkType, base: AnyLinkableHash, target: AnyLinkableHash, tag: LinkTag) -> ExternResult<ValidateCallbackResult> {
+  match link_type {
+    LudothequeLinkType::All => return Ok(ValidateCallbackResult::Valid),
+    LudothequeLinkType::NameIndex => return validate_name_index_link(base, target, tag),
+    LudothequeLinkType::Tagged => return validate_tagged_link(base, target, tag),
+    LudothequeLinkType::PlaysetTags => return validate_tag_link(target, base, tag),
+    LudothequeLinkType::Playsets => {},
+  }
+  let anchor_path = AnchorPath::from(PLAYSETS_ROOT_PATH);
+  let anchor: AnyLinkableHash = anchor_path.path_entry_hash()?.into();
+  /// Links created by `ensure()`: from the path root to the anchor, then from the anchor to a shard anchor
+  if target == anchor || base == anchor_root_hash()? {
+    if !anchor_path.is_ensure_link(&base, &target, &tag)? {
+      return Ok(ValidateCallbackResult::Invalid("Only the path root can link to the playsets anchor, tagged with its name".to_string()));
+    }
+    return Ok(ValidateCallbackResult::Valid);
+  }
+  if base == anchor {
+    return validate_shard_link(target, tag);
+  }
+  let Some(target_eh) = target.into_entry_hash()
+    else { return Ok(ValidateCallbackResult::Invalid("Playset link target must be an entry".to_string())) };
+  let Some(playset) = get_playset_entry(&target_eh)?
+    else { return Ok(ValidateCallbackResult::Invalid("Playset link target is not a Playset entry".to_string())) };
+  let shard_anchor: AnyLinkableHash = shard_path(PLAYSETS_ROOT_PATH, &name_shard(&playset.name)).path_entry_hash()?.into();
+  if base != shard_anchor {
+    return Ok(ValidateCallbackResult::Invalid("Playset link base must be the shard anchor of the playset's name".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// Links from the playsets anchor must go to the shard anchor named by their tag, as created by `ensure()`
+fn validate_shard_link(target: AnyLinkableHash, tag: LinkTag) -> ExternResult<ValidateCallbackResult> {
+  let maybe_shard = AnchorComponent::from_tag(&tag)
+    .and_then(|component| String::try_from(&component).ok());
+  let Some(shard) = maybe_shard
+    else { return Ok(ValidateCallbackResult::Invalid("Link tag from an anchor must be a shard component".to_string())) };
+  if name_shard(&shard) != shard {
+    return Ok(ValidateCallbackResult::Invalid(format!("'{}' is not a shard", shard)));
+  }
+  let path = shard_path(PLAYSETS_ROOT_PATH, &shard);
+  if target != path.path_entry_hash()?.into() || tag != path.make_tag()? {
+    return Ok(ValidateCallbackResult::Invalid(format!("Link from an anchor must go to the anchor of shard '{}'", shard)));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// Name index links are either the path links created by `ensure()` down to an index node,
+/// or links from the index node of a playset's name to that playset, tagged with the name.
+fn validate_name_index_link(base: AnyLinkableHash, target: AnyLinkableHash, tag: LinkTag) -> ExternResult<ValidateCallbackResult> {
+  let maybe_leaf = AnchorComponent::from_tag(&tag)
+    .and_then(|component| String::try_from(&component).ok());
+  if let Some(leaf) = maybe_leaf {
+    for path in name_index_paths_ending_with(NAME_INDEX_ROOT_PATH, "Playset", &leaf) {
+      if path.is_ensure_link(&base, &target, &tag)? {
+        return Ok(ValidateCallbackResult::Valid);
+      }
+    }
+  }
+  let Ok(name) = String::from_utf8(tag.into_inner())
+    else { return Ok(ValidateCallbackResult::Invalid("NameIndex link tag must be a path component or a name".to_string())) };
+  let node: AnyLinkableHash = name_index_path(NAME_INDEX_ROOT_PATH, "Playset", &normalize_name(&name)).path_entry_hash()?.into();
+  if base != node {
+    return Ok(ValidateCallbackResult::Invalid("NameIndex link base must be the index node of the tag's name".to_string()));
+  }
+  let Some(target_eh) = target.into_entry_hash()
+    else { return Ok(ValidateCallbackResult::Invalid("NameIndex link target must be an entry".to_string())) };
+  let Some(playset) = get_playset_entry(&target_eh)?
+    else { return Ok(ValidateCallbackResult::Invalid("NameIndex link target is not a Playset entry".to_string())) };
+  if playset.name != name {
+    return Ok(ValidateCallbackResult::Invalid("NameIndex link tag must be the playset's name".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// Tagged links are either the path links created by `ensure()` down to a tag anchor,
+/// or links from the anchor of a tag to a playset
+fn validate_tagged_link(base: AnyLinkableHash, target: AnyLinkableHash, tag: LinkTag) -> ExternResult<ValidateCallbackResult> {
+  let maybe_leaf = AnchorComponent::from_tag(&tag)
+    .and_then(|component| String::try_from(&component).ok());
+  if let Some(leaf) = maybe_leaf {
+    for path in tag_paths_ending_with(TAGS_ROOT_PATH, &leaf) {
+      if path.is_ensure_link(&base, &target, &tag)? {
+        return Ok(ValidateCallbackResult::Valid);
+      }
+    }
+  }
+  validate_tag_link(base, target, tag)
+}
+
+
+/// Tag links carry the tag as link tag and join the anchor of that tag and a playset
+fn validate_tag_link(anchor: AnyLinkableHash, playset: AnyLinkableHash, tag: LinkTag) -> ExternResult<ValidateCallbackResult> {
+  let Ok(tag) = String::from_utf8(tag.into_inner())
+    else { return Ok(ValidateCallbackResult::Invalid("Tag link tag must be a string".to_string())) };
+  let tag_anchor: AnyLinkableHash = tag_path(TAGS_ROOT_PATH, &tag).path_entry_hash()?.into();
+  if anchor != tag_anchor {
+    return Ok(ValidateCallbackResult::Invalid(format!("Tag link must be attached to the anchor of tag '{}'", tag)));
+  }
+  let Some(playset_eh) = playset.into_entry_hash()
+    else { return Ok(ValidateCallbackResult::Invalid("Tagged playset must be an entry".to_string())) };
+  if get_playset_entry(&playset_eh)?.is_none() {
+    return Ok(ValidateCallbackResult::Invalid("Tagged entry is not a Playset".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// The entry must deserialize into a Playset and hash back to the same hash
+pub fn get_playset_entry(eh: &EntryHash) -> ExternResult<Option<Playset>> {
+  let entry = must_get_entry(eh.clone())?.into_content();
+  let Ok(playset) = Playset::try_from(entry)
+    else { return Ok(None) };
+  if hash_entry(playset.clone())? != *eh {
+    return Ok(None);
+  }
+  Ok(Some(playset))
+}
+
+
+#[cfg(test)]
+mod tests;
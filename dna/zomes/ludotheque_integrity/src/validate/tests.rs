@@ -0,0 +1,92 @@
+use anchors::test_utils::*;
+
+use super::*;
+
+
+fn add_playset(name: &str) -> AnyLinkableHash {
+  let playset = Playset {
+    name: name.to_string(),
+    description: String::new(),
+    templates: Vec::new(),
+    svg_markers: Vec::new(),
+    emoji_groups: Vec::new(),
+    spaces: Vec::new(),
+  };
+  add_test_entry(playset).into()
+}
+
+fn hash_of(path: &AnchorPath) -> AnyLinkableHash {
+  path.path_entry_hash().unwrap().into()
+}
+
+fn is_valid(result: ExternResult<ValidateCallbackResult>) -> bool {
+  matches!(result.unwrap(), ValidateCallbackResult::Valid)
+}
+
+
+#[test]
+fn playset_link_must_come_from_the_shard_of_its_name() {
+  TestHdi::install();
+  let playset = add_playset("Games");
+  let shard_g = hash_of(&shard_path(PLAYSETS_ROOT_PATH, "g"));
+  let shard_h = hash_of(&shard_path(PLAYSETS_ROOT_PATH, "h"));
+  assert!(is_valid(validate_create_link(LudothequeLinkType::Playsets, shard_g, playset.clone(), LinkTag::from(()))));
+  assert!(!is_valid(validate_create_link(LudothequeLinkType::Playsets, shard_h, playset, LinkTag::from(()))));
+}
+
+
+#[test]
+fn ensure_links_must_follow_the_shard_path() {
+  TestHdi::install();
+  let anchor = AnchorPath::from(PLAYSETS_ROOT_PATH);
+  let shard = shard_path(PLAYSETS_ROOT_PATH, "g");
+  let root = anchor_root_hash().unwrap();
+  assert!(is_valid(validate_create_link(LudothequeLinkType::Playsets, root.clone(), hash_of(&anchor), anchor.make_tag().unwrap())));
+  assert!(!is_valid(validate_create_link(LudothequeLinkType::Playsets, root, hash_of(&shard), shard.make_tag().unwrap())));
+  assert!(is_valid(validate_create_link(LudothequeLinkType::Playsets, hash_of(&anchor), hash_of(&shard), shard.make_tag().unwrap())));
+  let other_shard = shard_path(PLAYSETS_ROOT_PATH, "h");
+  assert!(!is_valid(validate_create_link(LudothequeLinkType::Playsets, hash_of(&anchor), hash_of(&other_shard), shard.make_tag().unwrap())));
+}
+
+
+#[test]
+fn name_index_accepts_ensure_links_down_to_a_node() {
+  TestHdi::install();
+  let kind = name_index_path(NAME_INDEX_ROOT_PATH, "Playset", "");
+  let level_1 = name_index_path(NAME_INDEX_ROOT_PATH, "Playset", "g");
+  let level_2 = name_index_path(NAME_INDEX_ROOT_PATH, "Playset", "ga");
+  assert!(is_valid(validate_name_index_link(hash_of(&kind), hash_of(&level_1), level_1.make_tag().unwrap())));
+  assert!(is_valid(validate_name_index_link(hash_of(&level_1), hash_of(&level_2), level_2.make_tag().unwrap())));
+  let other = name_index_path(NAME_INDEX_ROOT_PATH, "Playset", "go");
+  assert!(!is_valid(validate_name_index_link(hash_of(&level_1), hash_of(&other), level_2.make_tag().unwrap())));
+}
+
+
+#[test]
+fn name_index_links_a_node_to_the_playsets_named_by_it() {
+  TestHdi::install();
+  let playset = add_playset("Games");
+  let node = name_index_path(NAME_INDEX_ROOT_PATH, "Playset", "games");
+  let name_tag = LinkTag::new("Games".as_bytes().to_vec());
+  assert!(is_valid(validate_name_index_link(hash_of(&node), playset.clone(), name_tag.clone())));
+  let other_node = name_index_path(NAME_INDEX_ROOT_PATH, "Playset", "go");
+  assert!(!is_valid(validate_name_index_link(hash_of(&other_node), playset.clone(), name_tag)));
+  let other_tag = LinkTag::new("Gamble".as_bytes().to_vec());
+  assert!(!is_valid(validate_name_index_link(hash_of(&node), playset, other_tag)));
+}
+
+
+#[test]
+fn tagged_links_are_ensure_links_or_go_from_a_tag_anchor_to_a_playset() {
+  TestHdi::install();
+  let playset = add_playset("Games");
+  let root = AnchorPath::from(TAGS_ROOT_PATH);
+  let anchor = tag_path(TAGS_ROOT_PATH, "board");
+  assert!(is_valid(validate_tagged_link(hash_of(&root), hash_of(&anchor), anchor.make_tag().unwrap())));
+  let board = LinkTag::new("board".as_bytes().to_vec());
+  assert!(is_valid(validate_tagged_link(hash_of(&anchor), playset.clone(), board.clone())));
+  assert!(!is_valid(validate_tagged_link(hash_of(&root), playset.clone(), board.clone())));
+  let other = tag_path(TAGS_ROOT_PATH, "cards");
+  assert!(!is_valid(validate_tagged_link(hash_of(&root), hash_of(&other), anchor.make_tag().unwrap())));
+  assert!(!is_valid(validate_tagged_link(hash_of(&other), playset, board)));
+}
@@ -3,7 +3,10 @@ use hdk::prelude::CellId;
 use holo_hash::EntryHashB64;
 
 use zome_utils::*;
+use playset_integrity::*;
 use crate::import_piece::ImportPieceInput;
+use crate::tags::*;
+use crate::pieces::*;
 
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -24,18 +27,50 @@ pub fn export_piece(input: ExportPieceInput) -> ExternResult<()> {
     && input.piece_type_name != "EmojiGroup" {
     return zome_error!("unknown piece type: '{}'", input.piece_type_name);
   }
-  let entry = get_entry_from_eh(input.piece_eh.into())?;
-  export_entry(&input.piece_type_name, entry, input.cell_id.clone())?;
-  Ok(())
+  export_piece_inner(&input.piece_type_name, input.piece_eh, input.cell_id)
+}
+
+
+/// Export a piece with its tags and the assets of its surface
+pub fn export_piece_inner(piece_type_name: &str, piece_eh: EntryHashB64, cell_id: CellId) -> ExternResult<()> {
+  let tags = get_piece_tags(piece_eh.clone())?;
+  let assets = get_surface_assets(piece_eh.clone())?;
+  for asset_eh in assets.clone() {
+    export_asset(ExportAssetInput {cell_id: cell_id.clone(), asset_eh})?;
+  }
+  let entry = get_entry_from_eh(piece_eh.into())?;
+  export_entry(piece_type_name, entry, tags, assets, cell_id)
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportAssetInput {
+  pub cell_id: CellId,
+  pub asset_eh: EntryHashB64,
+}
+
+/// Export the chunks of an asset, then its manifest
+#[hdk_extern]
+pub fn export_asset(input: ExportAssetInput) -> ExternResult<()> {
+  let asset_entry = get_entry_from_eh(input.asset_eh.into())?;
+  let asset = Asset::try_from(asset_entry.clone())?;
+  for chunk_eh in asset.chunks {
+    let chunk_entry = get_entry_from_eh(chunk_eh.into())?;
+    export_entry("AssetChunk", chunk_entry, vec![], vec![], input.cell_id.clone())?;
+  }
+  export_entry("Asset", asset_entry, vec![], vec![], input.cell_id)
 }
 
 
 ///
-pub fn export_entry(entry_type_name: &str, entry: Entry, cell_id: CellId) -> ExternResult<()> {
+pub fn export_entry(entry_type_name: &str, entry: Entry, tags: Vec<String>, assets: Vec<EntryHashB64>, cell_id: CellId) -> ExternResult<()> {
   debug!("export_entry(): {} - {:?}", entry_type_name, cell_id);
   let input = ImportPieceInput {
     piece_type_name: entry_type_name.to_string(),
     piece_entry: entry,
+    tags,
+    assets,
   };
   let res = call(
     CallTargetCell::OtherCell(cell_id),
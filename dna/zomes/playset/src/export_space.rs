@@ -25,9 +25,8 @@ pub struct ExportSpaceOutput {
 
 #[hdk_extern]
 fn export_space(ExportSpaceInput{cell_id, space_eh}: ExportSpaceInput) -> ExternResult<ExportSpaceOutput> {
-  let space_eh: EntryHash = space_eh.into();
-  let space_entry = get_entry_from_eh(space_eh)?;
-  let space = Space::try_from(space_entry.clone())?;
+  let space_entry = get_entry_from_eh(space_eh.clone().into())?;
+  let space = Space::try_from(space_entry)?;
   /// Export template
   let piece_info = ExportPieceInput {
     piece_eh: space.origin.clone(),
@@ -51,7 +50,7 @@ fn export_space(ExportSpaceInput{cell_id, space_eh}: ExportSpaceInput) -> Extern
     }
   }
   /// Export space
-  export_entry("Space", space_entry, cell_id.clone())?;
+  export_piece_inner("Space", space_eh, cell_id.clone())?;
   /// Done
   Ok(output)
 }
@@ -2,8 +2,7 @@ use hdk::prelude::*;
 use holo_hash::EntryHashB64;
 
 use playset_integrity::*;
-
-use crate::*;
+use anchors::*;
 
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -18,21 +17,17 @@ pub struct GetInventoryOutput {
 #[hdk_extern]
 pub fn get_inventory(_: ()) -> ExternResult<GetInventoryOutput> {
   /// Get templates
-  let base = get_templates_path().path_entry_hash()?;
-  let links = get_links(base, PlaysetLinkType::All, None)?;
-  let templates = links.iter().map(|link| link.target.clone().into_entry_hash().unwrap().into()).collect();
+  let links = get_all_links(TEMPLATES_ROOT_PATH, PlaysetLinkType::Templates)?;
+  let templates = links.into_iter().filter_map(|link| link.target.into_entry_hash()).map(Into::into).collect();
   /// Get svg_markers
-  let base = get_svg_marker_path().path_entry_hash()?;
-  let links = get_links(base, PlaysetLinkType::All, None)?;
-  let svg_markers = links.iter().map(|link| link.target.clone().into_entry_hash().unwrap().into()).collect();
+  let links = get_all_links(SVG_MARKERS_ROOT_PATH, PlaysetLinkType::SvgMarkers)?;
+  let svg_markers = links.into_iter().filter_map(|link| link.target.into_entry_hash()).map(Into::into).collect();
   /// Get emoji_groups
-  let base = get_emoji_group_path().path_entry_hash()?;
-  let links = get_links(base, PlaysetLinkType::All, None)?;
-  let emoji_groups = links.iter().map(|link| link.target.clone().into_entry_hash().unwrap().into()).collect();
+  let links = get_all_links(EMOJI_GROUPS_ROOT_PATH, PlaysetLinkType::EmojiGroups)?;
+  let emoji_groups = links.into_iter().filter_map(|link| link.target.into_entry_hash()).map(Into::into).collect();
   /// Get spaces
-  let base = get_spaces_path().path_entry_hash()?;
-  let links = get_links(base, PlaysetLinkType::All, None)?;
-  let spaces = links.iter().map(|link| link.target.clone().into_entry_hash().unwrap().into()).collect();
+  let links = get_all_links(SPACES_ROOT_PATH, PlaysetLinkType::Spaces)?;
+  let spaces = links.into_iter().filter_map(|link| link.target.into_entry_hash()).map(Into::into).collect();
   /// Done
   let inventory = GetInventoryOutput {
     spaces,
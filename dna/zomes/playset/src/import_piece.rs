@@ -1,4 +1,5 @@
 use hdk::prelude::*;
+use holo_hash::EntryHashB64;
 use zome_utils::*;
 use playset_integrity::*;
 use crate::*;
@@ -8,39 +9,56 @@ use crate::*;
 pub struct ImportPieceInput {
   pub piece_type_name: String, // FIXME use UnitEntryTypes and AppEntryTypeName
   pub piece_entry: Entry,
+  #[serde(default)]
+  pub tags: Vec<String>,
+  #[serde(default)]
+  pub assets: Vec<EntryHashB64>,
 }
 
 /// Zome Function Callback
 /// Should not be called directly. Only via remote call to self by ludotheque
 #[hdk_extern]
-fn import_piece(ImportPieceInput{piece_entry, piece_type_name}: ImportPieceInput) -> ExternResult<()> {
+fn import_piece(ImportPieceInput{piece_entry, piece_type_name, tags, assets}: ImportPieceInput) -> ExternResult<()> {
   debug!("import_piece() entry_def_id = {:?} | {}", piece_type_name, zome_info()?.name);
-  /// Bail if we already have it
+  /// Import depending on type, unless we already have it
   let eh = hash_entry(piece_entry.clone())?;
-  let maybe_entry = get_entry_from_eh(eh);
-  if maybe_entry.is_ok() {
-    return Ok(());
+  let maybe_entry = get_entry_from_eh(eh.clone());
+  if maybe_entry.is_err() {
+    match piece_type_name.as_str() {
+      "Template" => {
+        let template = Template::try_from(piece_entry)?;
+        create_template(template)?;
+      },
+      "Space" => {
+        let space = Space::try_from(piece_entry)?;
+        create_space(space)?;
+      },
+      "SvgMarker" => {
+        let e = SvgMarker::try_from(piece_entry)?;
+        create_svg_marker(e)?;
+      },
+      "EmojiGroup" => {
+        let e = EmojiGroup::try_from(piece_entry)?;
+        create_emoji_group(e)?;
+      },
+      "AssetChunk" => {
+        let e = AssetChunk::try_from(piece_entry)?;
+        create_entry(PlaysetEntry::AssetChunk(e))?;
+      },
+      "Asset" => {
+        let e = Asset::try_from(piece_entry)?;
+        create_asset_manifest(e)?;
+      },
+      _ => return error(&format!("Unknown entry type: {}", piece_type_name)),
+    };
+  }
+  /// Apply tags and surface assets, also on pieces we already had
+  for tag in tags {
+    tag_piece(TagPieceInput {piece_eh: eh.clone().into(), tag})?;
+  }
+  for asset_eh in assets {
+    attach_asset(AttachAssetInput {piece_eh: eh.clone().into(), asset_eh})?;
   }
-  /// Import depending on type
-  match piece_type_name.as_str() {
-    "Template" => {
-      let template = Template::try_from(piece_entry)?;
-      create_template(template)?;
-    },
-    "Space" => {
-      let space = Space::try_from(piece_entry)?;
-      create_space(space)?;
-    },
-    "SvgMarker" => {
-      let e = SvgMarker::try_from(piece_entry)?;
-      create_svg_marker(e)?;
-    },
-    "EmojiGroup" => {
-      let e = EmojiGroup::try_from(piece_entry)?;
-      create_emoji_group(e)?;
-    },
-    _ => return error(&format!("Unknown entry type: {}", piece_type_name)),
-  };
   /// Done
   Ok(())
 }
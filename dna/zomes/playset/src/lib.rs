@@ -3,12 +3,20 @@
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 #![allow(unused_attributes)]
+#![allow(clippy::redundant_static_lifetimes)]
+#![allow(clippy::empty_docs)]
+#![allow(clippy::empty_line_after_doc_comments)]
+#![allow(clippy::empty_line_after_outer_attr)]
 
 mod pieces;
+mod name_index;
+mod tags;
 mod import_piece;
 mod export_piece;
 mod export_space;
 mod get_inventory;
 
 pub use pieces::*;
+pub use name_index::*;
+pub use tags::*;
 pub use export_piece::*;
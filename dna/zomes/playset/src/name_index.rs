@@ -0,0 +1,95 @@
+use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use zome_utils::*;
+
+use playset_integrity::*;
+use anchors::*;
+
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPiecesInput {
+  pub query: String,
+  pub kinds: Vec<String>, // piece type names, all kinds if empty
+  pub limit: u32,
+}
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PieceSearchResult {
+  pub piece_type_name: String,
+  pub hash: EntryHashB64,
+  pub name: String,
+}
+
+
+/// Link a new piece from the index node of its name
+pub fn index_piece_name(piece_type_name: &str, name: &str, eh: EntryHash) -> ExternResult<ActionHash> {
+  let path = name_index_path(NAME_INDEX_ROOT_PATH, piece_type_name, &normalize_name(name))
+    .typed(PlaysetLinkType::NameIndex)?;
+  path.ensure()?;
+  create_link(path.path_entry_hash()?, eh, PlaysetLinkType::NameIndex, LinkTag::new(name.as_bytes().to_vec()))
+}
+
+
+/// Case-insensitive search of pieces whose name starts with the query
+#[hdk_extern]
+fn search_pieces(input: SearchPiecesInput) -> ExternResult<Vec<PieceSearchResult>> {
+  let query = normalize_name(&input.query);
+  let kinds: Vec<String> = if input.kinds.is_empty() {
+    INDEXED_PIECE_TYPES.iter().map(|kind| kind.to_string()).collect()
+  } else {
+    input.kinds
+  };
+  let mut results = Vec::new();
+  for kind in kinds {
+    if !INDEXED_PIECE_TYPES.contains(&kind.as_str()) {
+      return zome_error!("unknown piece type: '{}'", kind);
+    }
+    let node = name_index_path(NAME_INDEX_ROOT_PATH, &kind, &query).typed(PlaysetLinkType::NameIndex)?;
+    for (eh, name) in search_index_node(&node, &query, (input.limit as usize).saturating_sub(results.len()))? {
+      results.push(PieceSearchResult {piece_type_name: kind.clone(), hash: eh.into(), name});
+    }
+    if results.len() >= input.limit as usize {
+      break;
+    }
+  }
+  Ok(results)
+}
+
+
+/// Collect matching names under an index node, descending into children
+/// while the query is shorter than the index depth
+fn search_index_node(node: &TypedPath, query: &str, limit: usize) -> ExternResult<Vec<(EntryHash, String)>> {
+  let mut found = Vec::new();
+  if limit == 0 {
+    return Ok(found);
+  }
+  let links = get_links(node.path_entry_hash()?, PlaysetLinkType::NameIndex, None)?;
+  for link in links {
+    let Ok(name) = String::from_utf8(link.tag.into_inner())
+      else {continue};
+    let Some(eh) = link.target.into_entry_hash()
+      else {continue};
+    if !normalize_name(&name).starts_with(query) {
+      continue;
+    }
+    found.push((eh, name));
+    if found.len() >= limit {
+      return Ok(found);
+    }
+  }
+  if node.path.as_ref().len() >= NAME_INDEX_DEPTH + 2 {
+    return Ok(found);
+  }
+  for child in node.children_paths()? {
+    let mut sub = search_index_node(&child, query, limit - found.len())?;
+    found.append(&mut sub);
+    if found.len() >= limit {
+      break;
+    }
+  }
+  Ok(found)
+}
@@ -0,0 +1,138 @@
+use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use zome_utils::*;
+use playset_integrity::*;
+
+use anchors::*;
+
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAssetInput {
+    pub name: String,
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct AssetOutput {
+    manifest: Asset,
+    data: Vec<u8>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct AssetManifestOutput {
+    hash: EntryHashB64,
+    manifest: Asset,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachAssetInput {
+    pub piece_eh: EntryHashB64,
+    pub asset_eh: EntryHashB64,
+}
+
+
+/// Split the data into chunks and commit them with their manifest
+#[hdk_extern]
+pub fn create_asset(input: CreateAssetInput) -> ExternResult<EntryHashB64> {
+    let mut chunks = Vec::new();
+    for data in input.data.chunks(ASSET_CHUNK_SIZE) {
+        let chunk = AssetChunk {data: data.to_vec()};
+        create_entry(PlaysetEntry::AssetChunk(chunk.clone()))?;
+        chunks.push(hash_entry(chunk)?.into());
+    }
+    let asset = Asset {
+        name: input.name,
+        mime_type: input.mime_type,
+        size: input.data.len() as u64,
+        hash: asset_hash(&input.data)?,
+        chunks,
+    };
+    create_asset_manifest(asset)
+}
+
+
+/// Commit and list the manifest of an asset whose chunks are already committed
+pub fn create_asset_manifest(asset: Asset) -> ExternResult<EntryHashB64> {
+    let _hh = create_entry(PlaysetEntry::Asset(asset.clone()))?;
+    let eh = hash_entry(asset.clone())?;
+    link_to_shard(ASSETS_ROOT_PATH, &asset.name, PlaysetLinkType::Assets, eh.clone())?;
+    Ok(eh.into())
+}
+
+
+/// Manifest and reassembled data of an asset
+#[hdk_extern]
+fn get_asset(asset_eh: EntryHashB64) -> ExternResult<Option<AssetOutput>> {
+    let maybe_record = get(asset_eh, GetOptions::content())?;
+    let Some(record) = maybe_record
+        else {return Ok(None)};
+    let manifest = get_typed_from_record::<Asset>(record)?;
+    let mut data = Vec::with_capacity(manifest.size as usize);
+    for chunk_eh in manifest.chunks.clone() {
+        let mut chunk: AssetChunk = get_typed_from_eh(chunk_eh.into())?;
+        data.append(&mut chunk.data);
+    }
+    Ok(Some(AssetOutput {manifest, data}))
+}
+
+
+/// Manifests of every asset, without their data
+#[hdk_extern]
+fn get_all_assets(_: ()) -> ExternResult<Vec<AssetManifestOutput>> {
+    let links = get_all_links(ASSETS_ROOT_PATH, PlaysetLinkType::Assets)?;
+    get_assets_inner(links)
+}
+
+#[hdk_extern]
+fn get_assets_page(input: GetPageInput) -> ExternResult<PageOutput<AssetManifestOutput>> {
+    let (links, maybe_next_cursor) = get_page_links(ASSETS_ROOT_PATH, PlaysetLinkType::Assets, &input)?;
+    let items = get_assets_inner(links)?;
+    Ok(PageOutput {items, maybe_next_cursor})
+}
+
+fn get_assets_inner(links: Vec<Link>) -> ExternResult<Vec<AssetManifestOutput>> {
+    let mut assets = vec![];
+    for link in links {
+        let Some(eh) = link.target.into_entry_hash()
+            else {continue};
+        let Ok(manifest) = get_typed_from_eh::<Asset>(eh.clone())
+            else {continue};
+        assets.push(AssetManifestOutput {hash: eh.into(), manifest});
+    }
+    Ok(assets)
+}
+
+
+/// Declare that the surface of a template or space displays an asset.
+/// Surfaces reference their assets only through these SurfaceAssets links,
+/// the surface JSON does not hold asset hashes.
+#[hdk_extern]
+pub fn attach_asset(input: AttachAssetInput) -> ExternResult<()> {
+    let piece_eh: EntryHash = input.piece_eh.clone().into();
+    let asset_eh: EntryHash = input.asset_eh.clone().into();
+    if get_surface_assets(input.piece_eh)?.contains(&input.asset_eh) {
+        return Ok(());
+    }
+    create_link(piece_eh, asset_eh, PlaysetLinkType::SurfaceAssets, LinkTag::from(()))?;
+    Ok(())
+}
+
+
+/// Assets displayed by the surface of a template or space
+#[hdk_extern]
+pub fn get_surface_assets(piece_eh: EntryHashB64) -> ExternResult<Vec<EntryHashB64>> {
+    let links = get_links(EntryHash::from(piece_eh), PlaysetLinkType::SurfaceAssets, None)?;
+    let mut assets: Vec<EntryHashB64> = Vec::new();
+    for link in links {
+        let Some(eh) = link.target.into_entry_hash()
+            else {continue};
+        let eh64: EntryHashB64 = eh.into();
+        if !assets.contains(&eh64) {
+            assets.push(eh64);
+        }
+    }
+    Ok(assets)
+}
@@ -1,9 +1,11 @@
 use hdk::prelude::*;
-use hdk::hash_path::path::TypedPath;
 use holo_hash::EntryHashB64;
 use zome_utils::*;
 use playset_integrity::*;
 
+use anchors::*;
+use crate::name_index::*;
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct EmojiGroupOutput {
     hash: EntryHashB64,
@@ -11,21 +13,13 @@ pub struct EmojiGroupOutput {
 }
 
 
-///
-pub fn get_emoji_group_path() -> TypedPath {
-    Path::from("emoji-groups").typed(PlaysetLinkType::EmojiGroups).unwrap()
-}
-
-
 ///
 #[hdk_extern]
 pub fn create_emoji_group(input: EmojiGroup) -> ExternResult<EntryHashB64> {
     let _hh = create_entry(&PlaysetEntry::EmojiGroup(input.clone()))?;
     let eh = hash_entry(input.clone())?;
-    let path = get_emoji_group_path();
-    path.ensure()?;
-    let anchor_eh = path.path_entry_hash()?;
-    create_link(anchor_eh, eh.clone(), PlaysetLinkType::All, LinkTag::from(()))?;
+    link_to_shard(EMOJI_GROUPS_ROOT_PATH, &input.name, PlaysetLinkType::EmojiGroups, eh.clone())?;
+    index_piece_name("EmojiGroup", &input.name, eh.clone())?;
     let eh64: EntryHashB64 = eh.clone().into();
     // let me = agent_info()?.agent_latest_pubkey.into();
     // emit_signal(&SignalPayload::new(None, me, Message::NewEmojiGroup((eh64.clone(), input))))?;
@@ -47,17 +41,28 @@ fn get_emoji_group(input: EntryHashB64) -> ExternResult<Option<EmojiGroup>> {
 ///
 #[hdk_extern]
 fn get_all_emoji_groups(_: ()) -> ExternResult<Vec<EmojiGroupOutput>> {
-    let path = get_emoji_group_path();
-    let groups = get_all_inner(path.path_entry_hash()?)?;
+    let links = get_all_links(EMOJI_GROUPS_ROOT_PATH, PlaysetLinkType::EmojiGroups)?;
+    let groups = get_all_inner(links)?;
     Ok(groups)
 }
 
-fn get_all_inner(base: EntryHash) -> ExternResult<Vec<EmojiGroupOutput>> {
-    let entries = get_typed_from_links(base, PlaysetLinkType::All, None)
-      .map_err(|err| wasm_error!(WasmErrorInner::Guest(err.to_string())))?;
+
+///
+#[hdk_extern]
+fn get_emoji_groups_page(input: GetPageInput) -> ExternResult<PageOutput<EmojiGroupOutput>> {
+    let (links, maybe_next_cursor) = get_page_links(EMOJI_GROUPS_ROOT_PATH, PlaysetLinkType::EmojiGroups, &input)?;
+    let items = get_all_inner(links)?;
+    Ok(PageOutput {items, maybe_next_cursor})
+}
+
+fn get_all_inner(links: Vec<Link>) -> ExternResult<Vec<EmojiGroupOutput>> {
     let mut groups = vec![];
-    for pair in entries {
-        groups.push(EmojiGroupOutput {hash: hash_entry(&pair.0)?.into(), content: pair.0});
+    for link in links {
+        let Some(eh) = link.target.into_entry_hash()
+            else {continue};
+        let Ok(content) = get_typed_from_eh::<EmojiGroup>(eh.clone())
+            else {continue};
+        groups.push(EmojiGroupOutput {hash: eh.into(), content});
     }
     Ok(groups)
 }
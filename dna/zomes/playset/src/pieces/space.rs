@@ -1,14 +1,11 @@
 use hdk::prelude::*;
-use hdk::hash_path::path::TypedPath;
 use zome_utils::*;
 use holo_hash::EntryHashB64;
 
 use playset_integrity::*;
 
-
-pub fn get_spaces_path() -> TypedPath {
-    Path::from("spaces").typed(PlaysetLinkType::Spaces).unwrap()
-}
+use anchors::*;
+use crate::name_index::*;
 
 
 ///
@@ -17,10 +14,13 @@ pub fn create_space(input: Space) -> ExternResult<EntryHashB64> {
     debug!("create_space(): {:?}", input);
     let _hh = create_entry(PlaysetEntry::Space(input.clone()))?;
     let space_eh = hash_entry(input.clone())?;
-    let path = get_spaces_path();
-    path.ensure()?;
-    let anchor_eh = path.path_entry_hash()?;
-    create_link(anchor_eh, space_eh.clone(), PlaysetLinkType::All, LinkTag::from(()))?;
+    link_to_shard(SPACES_ROOT_PATH, &input.name, PlaysetLinkType::Spaces, space_eh.clone())?;
+    index_piece_name("Space", &input.name, space_eh.clone())?;
+    /// Reverse links so we can find the spaces depending on a template or marker
+    create_link(EntryHash::from(input.origin.clone()), space_eh.clone(), PlaysetLinkType::TemplateSpaces, LinkTag::from(()))?;
+    if let Some(marker_piece) = &input.maybe_marker_piece {
+        create_link(marker_piece.eh(), space_eh.clone(), PlaysetLinkType::MarkerSpaces, LinkTag::from(()))?;
+    }
     let eh64: EntryHashB64 = space_eh.clone().into();
     // let me = agent_info()?.agent_latest_pubkey.into();
     // emit_signal(&SignalPayload::new(None, me, Message::NewSpace(eh64.clone())))?;
@@ -49,19 +49,191 @@ pub struct SpaceOutput {
 ///
 #[hdk_extern]
 fn get_spaces(_: ()) -> ExternResult<Vec<SpaceOutput>> {
-    let path = get_spaces_path();
-    let anchor_eh = path.path_entry_hash()?;
-    let spaces = get_spaces_inner(anchor_eh)?;
+    let links = get_all_links(SPACES_ROOT_PATH, PlaysetLinkType::Spaces)?;
+    let spaces = get_spaces_inner(links)?;
+    Ok(spaces)
+}
+
+///
+#[hdk_extern]
+fn get_spaces_page(input: GetPageInput) -> ExternResult<PageOutput<SpaceOutput>> {
+    let (links, maybe_next_cursor) = get_page_links(SPACES_ROOT_PATH, PlaysetLinkType::Spaces, &input)?;
+    let items = get_spaces_inner(links)?;
+    Ok(PageOutput {items, maybe_next_cursor})
+}
+
+///
+#[hdk_extern]
+fn get_spaces_using_template(template_eh: EntryHashB64) -> ExternResult<Vec<EntryHashB64>> {
+    get_dependent_spaces(template_eh.into(), PlaysetLinkType::TemplateSpaces)
+}
+
+///
+#[hdk_extern]
+fn get_spaces_using_marker(marker_eh: EntryHashB64) -> ExternResult<Vec<EntryHashB64>> {
+    get_dependent_spaces(marker_eh.into(), PlaysetLinkType::MarkerSpaces)
+}
+
+fn get_dependent_spaces(piece_eh: EntryHash, link_type: PlaysetLinkType) -> ExternResult<Vec<EntryHashB64>> {
+    let links = get_links(piece_eh, link_type, None)?;
+    let mut spaces: Vec<EntryHashB64> = Vec::new();
+    for link in links {
+        let Some(eh) = link.target.into_entry_hash()
+            else {continue};
+        let eh64: EntryHashB64 = eh.into();
+        if !spaces.contains(&eh64) {
+            spaces.push(eh64);
+        }
+    }
     Ok(spaces)
 }
 
-fn get_spaces_inner(base: EntryHash) -> ExternResult<Vec<SpaceOutput>> {
-    let entries = get_typed_from_links(base, PlaysetLinkType::All, None)
-      .map_err(|err| wasm_error!(WasmErrorInner::Guest(err.to_string())))?;
+fn get_spaces_inner(links: Vec<Link>) -> ExternResult<Vec<SpaceOutput>> {
     let mut spaces = vec![];
-    for pair in entries {
-        spaces.push(SpaceOutput {hash: hash_entry(&pair.0)?.into(), content: pair.0});
+    for link in links {
+        let Some(eh) = link.target.into_entry_hash()
+            else {continue};
+        let Ok(content) = get_typed_from_eh::<Space>(eh.clone())
+            else {continue};
+        spaces.push(SpaceOutput {hash: eh.into(), content});
     }
     Ok(spaces)
 }
 
+
+
+
+///
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSpaceInput {
+    pub space_eh: EntryHashB64,
+    pub new_space: Space,
+}
+
+/// Create a new revision of a space, e.g. with a resized surface.
+/// Sessions stay on the revision they were created on, see `migrate_heres` in the where zome to move their Heres.
+#[hdk_extern]
+fn update_space(input: UpdateSpaceInput) -> ExternResult<EntryHashB64> {
+    let new_eh = create_space(input.new_space)?;
+    create_link(EntryHash::from(input.space_eh), EntryHash::from(new_eh.clone()), PlaysetLinkType::SpaceUpdates, LinkTag::from(()))?;
+    Ok(new_eh)
+}
+
+/// Revisions of a space after the given one, oldest first, following the earliest update at each step
+#[hdk_extern]
+fn get_space_revisions(space_eh: EntryHashB64) -> ExternResult<Vec<EntryHashB64>> {
+    let mut revisions: Vec<EntryHashB64> = Vec::new();
+    let mut current: EntryHash = space_eh.clone().into();
+    loop {
+        let mut links = get_links(current, PlaysetLinkType::SpaceUpdates, None)?;
+        links.sort_by_key(|link| link.timestamp);
+        let Some(next) = links.into_iter().filter_map(|link| link.target.into_entry_hash()).next()
+            else {break};
+        let next64: EntryHashB64 = next.clone().into();
+        if next64 == space_eh || revisions.contains(&next64) {
+            break;
+        }
+        revisions.push(next64);
+        current = next;
+    }
+    Ok(revisions)
+}
+
+
+/// Max number of ancestors returned, guarding against cycles
+pub const MAX_SPACE_DEPTH: usize = 16;
+
+///
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachSubSpaceInput {
+    pub parent_eh: EntryHashB64,
+    pub child_eh: EntryHashB64,
+    pub attachment: SpaceAttachment,
+}
+
+///
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SubSpaceOutput {
+    pub space_eh: EntryHashB64,
+    pub attachment: SpaceAttachment,
+}
+
+
+/// Attach a space as a sub-space of another, on a zone or point of the parent's surface
+#[hdk_extern]
+fn attach_sub_space(input: AttachSubSpaceInput) -> ExternResult<()> {
+    let parent_eh: EntryHash = input.parent_eh.into();
+    let child_eh: EntryHash = input.child_eh.into();
+    let tag = input.attachment.to_tag()?;
+    create_link(parent_eh.clone(), child_eh.clone(), PlaysetLinkType::SubSpaces, tag.clone())?;
+    create_link(child_eh, parent_eh, PlaysetLinkType::ParentSpaces, tag)?;
+    Ok(())
+}
+
+/// Remove our links between a space and its sub-space
+#[hdk_extern]
+fn detach_sub_space(input: AttachSubSpaceInput) -> ExternResult<()> {
+    let parent_eh: EntryHash = input.parent_eh.into();
+    let child_eh: EntryHash = input.child_eh.into();
+    let me = agent_info()?.agent_latest_pubkey;
+    let pairs = [
+        (parent_eh.clone(), child_eh.clone(), PlaysetLinkType::SubSpaces),
+        (child_eh, parent_eh, PlaysetLinkType::ParentSpaces),
+    ];
+    for (base, target, link_type) in pairs {
+        for link in get_links(base, link_type, None)? {
+            if link.target == AnyLinkableHash::from(target.clone()) && link.author == me {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Sub-spaces of a space with where they are attached
+#[hdk_extern]
+pub fn get_sub_spaces(space_eh: EntryHashB64) -> ExternResult<Vec<SubSpaceOutput>> {
+    get_related_spaces(space_eh.into(), PlaysetLinkType::SubSpaces)
+}
+
+/// Parents of a space with where it is attached on them
+#[hdk_extern]
+fn get_parent_spaces(space_eh: EntryHashB64) -> ExternResult<Vec<SubSpaceOutput>> {
+    get_related_spaces(space_eh.into(), PlaysetLinkType::ParentSpaces)
+}
+
+/// Chain of ancestors of a space, nearest first, following the first parent at each level
+#[hdk_extern]
+fn get_space_ancestors(space_eh: EntryHashB64) -> ExternResult<Vec<EntryHashB64>> {
+    let mut ancestors: Vec<EntryHashB64> = Vec::new();
+    let mut current = space_eh.clone();
+    while ancestors.len() < MAX_SPACE_DEPTH {
+        let parents = get_related_spaces(current.into(), PlaysetLinkType::ParentSpaces)?;
+        let Some(parent) = parents.into_iter().next()
+            else {break};
+        if parent.space_eh == space_eh || ancestors.contains(&parent.space_eh) {
+            break;
+        }
+        ancestors.push(parent.space_eh.clone());
+        current = parent.space_eh;
+    }
+    Ok(ancestors)
+}
+
+fn get_related_spaces(space_eh: EntryHash, link_type: PlaysetLinkType) -> ExternResult<Vec<SubSpaceOutput>> {
+    let mut links = get_links(space_eh, link_type, None)?;
+    links.sort_by_key(|link| link.timestamp);
+    let mut related: Vec<SubSpaceOutput> = Vec::new();
+    for link in links {
+        let (Some(eh), Some(attachment)) = (link.target.into_entry_hash(), SpaceAttachment::from_tag(link.tag))
+            else {continue};
+        let eh64: EntryHashB64 = eh.into();
+        if !related.iter().any(|other| other.space_eh == eh64) {
+            related.push(SubSpaceOutput {space_eh: eh64, attachment});
+        }
+    }
+    Ok(related)
+}
@@ -1,27 +1,35 @@
 use hdk::prelude::*;
-use hdk::hash_path::path::TypedPath;
 use zome_utils::*;
 use holo_hash::EntryHashB64;
 use playset_integrity::*;
 
+use anchors::*;
+use crate::name_index::*;
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct SvgMarkerOutput {
     hash: EntryHashB64,
     content: SvgMarker,
 }
 
-pub fn get_svg_marker_path() -> TypedPath {
-    Path::from("svg-marker").typed(PlaysetLinkType::SvgMarkers).unwrap()
+/// Placeholders substituted in the value of an SvgMarker by `render_svg_marker()`
+pub const COLOR_PLACEHOLDER: &'static str = "%%color%%";
+pub const INITIALS_PLACEHOLDER: &'static str = "%%initials%%";
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderSvgMarkerInput {
+    pub svg_marker_eh: EntryHashB64,
+    pub color: String,
+    pub initials: String,
 }
 
 #[hdk_extern]
 pub fn create_svg_marker(input: SvgMarker) -> ExternResult<EntryHashB64> {
     let _hh = create_entry(PlaysetEntry::SvgMarker(input.clone()))?;
     let eh = hash_entry(input.clone())?;
-    let path = get_svg_marker_path();
-    path.ensure()?;
-    let anchor_eh = path.path_entry_hash()?;
-    create_link(anchor_eh, eh.clone(), PlaysetLinkType::All, LinkTag::from(()))?;
+    link_to_shard(SVG_MARKERS_ROOT_PATH, &input.name, PlaysetLinkType::SvgMarkers, eh.clone())?;
+    index_piece_name("SvgMarker", &input.name, eh.clone())?;
     let eh64: EntryHashB64 = eh.clone().into();
     // let me = agent_info()?.agent_latest_pubkey.into();
     // emit_signal(&SignalPayload::new(None, me, Message::NewSvgMarker((eh64.clone(), input))))?;
@@ -37,19 +45,45 @@ fn get_svg_marker(input: EntryHashB64) -> ExternResult<Option<SvgMarker>> {
     Ok(Some(typed))
 }
 
+/// Value of an SvgMarker with its color and initials substituted
+#[hdk_extern]
+fn render_svg_marker(input: RenderSvgMarkerInput) -> ExternResult<String> {
+    let Some(marker) = get_svg_marker(input.svg_marker_eh.clone())?
+        else { return zome_error!("SvgMarker not found: {}", input.svg_marker_eh) };
+    let svg = marker.value
+        .replace(COLOR_PLACEHOLDER, &escape_xml(&input.color))
+        .replace(INITIALS_PLACEHOLDER, &escape_xml(&input.initials));
+    Ok(svg)
+}
+
 #[hdk_extern]
 fn get_svg_markers(_: ()) -> ExternResult<Vec<SvgMarkerOutput>> {
-    let path = get_svg_marker_path();
-    let templates = get_inner(path.path_entry_hash()?)?;
-    Ok(templates)
+    let links = get_all_links(SVG_MARKERS_ROOT_PATH, PlaysetLinkType::SvgMarkers)?;
+    let markers = get_inner(links)?;
+    Ok(markers)
 }
 
-fn get_inner(base: EntryHash) -> ExternResult<Vec<SvgMarkerOutput>> {
-    let entries = get_typed_from_links(base, PlaysetLinkType::All, None)
-      .map_err(|err| wasm_error!(WasmErrorInner::Guest(err.to_string())))?;
-    let mut templates = vec![];
-    for pair in entries {
-        templates.push(SvgMarkerOutput {hash: hash_entry(&pair.0)?.into(), content: pair.0});
+#[hdk_extern]
+fn get_svg_markers_page(input: GetPageInput) -> ExternResult<PageOutput<SvgMarkerOutput>> {
+    let (links, maybe_next_cursor) = get_page_links(SVG_MARKERS_ROOT_PATH, PlaysetLinkType::SvgMarkers, &input)?;
+    let items = get_inner(links)?;
+    Ok(PageOutput {items, maybe_next_cursor})
+}
+
+fn get_inner(links: Vec<Link>) -> ExternResult<Vec<SvgMarkerOutput>> {
+    let mut markers = vec![];
+    for link in links {
+        let Some(eh) = link.target.into_entry_hash()
+            else {continue};
+        let Ok(content) = get_typed_from_eh::<SvgMarker>(eh.clone())
+            else {continue};
+        markers.push(SvgMarkerOutput {hash: eh.into(), content});
     }
-    Ok(templates)
+    Ok(markers)
+}
+
+
+///
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
 }
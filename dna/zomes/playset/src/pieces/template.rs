@@ -1,9 +1,11 @@
 use hdk::prelude::*;
-use hdk::hash_path::path::TypedPath;
 use holo_hash::EntryHashB64;
 use zome_utils::*;
 use playset_integrity::*;
 
+use anchors::*;
+use crate::name_index::*;
+
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct TemplateOutput {
@@ -12,21 +14,13 @@ pub struct TemplateOutput {
 }
 
 
-///
-pub fn get_templates_path() -> TypedPath {
-    Path::from("templates").typed(PlaysetLinkType::Templates).unwrap()
-}
-
-
 ///
 #[hdk_extern]
 pub fn create_template(input: Template) -> ExternResult<EntryHashB64> {
     let _hh = create_entry(PlaysetEntry::Template(input.clone()))?;
     let eh = hash_entry(input.clone())?;
-    let path = get_templates_path();
-    path.ensure()?;
-    let anchor_eh = path.path_entry_hash()?;
-    create_link(anchor_eh, eh.clone(), PlaysetLinkType::All, LinkTag::from(()))?;
+    link_to_shard(TEMPLATES_ROOT_PATH, &input.name, PlaysetLinkType::Templates, eh.clone())?;
+    index_piece_name("Template", &input.name, eh.clone())?;
     let eh64: EntryHashB64 = eh.clone().into();
     // let me = agent_info()?.agent_latest_pubkey.into();
     // emit_signal(&SignalPayload::new(None, me, Message::NewTemplate((eh64.clone(), input))))?;
@@ -44,17 +38,26 @@ fn get_template(input: EntryHashB64) -> ExternResult<Option<Template>> {
 
 #[hdk_extern]
 fn get_templates(_: ()) -> ExternResult<Vec<TemplateOutput>> {
-    let path = get_templates_path();
-    let templates = get_templates_inner(path.path_entry_hash()?)?;
+    let links = get_all_links(TEMPLATES_ROOT_PATH, PlaysetLinkType::Templates)?;
+    let templates = get_templates_inner(links)?;
     Ok(templates)
 }
 
-fn get_templates_inner(base: EntryHash) -> ExternResult<Vec<TemplateOutput>> {
-    let entries = get_typed_from_links(base, PlaysetLinkType::All, None)
-      .map_err(|err| wasm_error!(WasmErrorInner::Guest(err.to_string())))?;
+#[hdk_extern]
+fn get_templates_page(input: GetPageInput) -> ExternResult<PageOutput<TemplateOutput>> {
+    let (links, maybe_next_cursor) = get_page_links(TEMPLATES_ROOT_PATH, PlaysetLinkType::Templates, &input)?;
+    let items = get_templates_inner(links)?;
+    Ok(PageOutput {items, maybe_next_cursor})
+}
+
+fn get_templates_inner(links: Vec<Link>) -> ExternResult<Vec<TemplateOutput>> {
     let mut templates = vec![];
-    for pair in entries {
-        templates.push(TemplateOutput {hash: hash_entry(&pair.0)?.into(), content: pair.0});
+    for link in links {
+        let Some(eh) = link.target.into_entry_hash()
+            else {continue};
+        let Ok(content) = get_typed_from_eh::<Template>(eh.clone())
+            else {continue};
+        templates.push(TemplateOutput {hash: eh.into(), content});
     }
     Ok(templates)
 }
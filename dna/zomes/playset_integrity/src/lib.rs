@@ -3,9 +3,33 @@
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 #![allow(unused_attributes)]
+#![allow(clippy::redundant_static_lifetimes)]
+#![allow(clippy::empty_docs)]
+#![allow(clippy::empty_line_after_doc_comments)]
+#![allow(clippy::empty_line_after_outer_attr)]
 
 pub mod pieces;
+mod validate;
+
 pub use pieces::*;
+pub use validate::*;
+
+
+///-------------------------------------------------------------------------------------------------
+/// Global consts
+///-------------------------------------------------------------------------------------------------
+/// Root path of each piece kind's anchor
+pub const TEMPLATES_ROOT_PATH: &'static str = "templates";
+pub const SVG_MARKERS_ROOT_PATH: &'static str = "svg-marker";
+pub const EMOJI_GROUPS_ROOT_PATH: &'static str = "emoji-groups";
+pub const SPACES_ROOT_PATH: &'static str = "spaces";
+pub const ASSETS_ROOT_PATH: &'static str = "assets";
+/// Root path of the name-prefix index of pieces
+pub const NAME_INDEX_ROOT_PATH: &'static str = "name_index";
+/// Type names of the pieces maintained in the name index
+pub const INDEXED_PIECE_TYPES: [&'static str; 4] = ["Template", "SvgMarker", "EmojiGroup", "Space"];
+/// Root path of the tag anchors
+pub const TAGS_ROOT_PATH: &'static str = "tags";
 
 
 ///-------------------------------------------------------------------------------------------------
@@ -25,6 +49,10 @@ pub enum PlaysetEntry {
     Template(Template),
     #[entry_def(required_validations = 2, visibility = "public")]
     Space(Space),
+    #[entry_def(required_validations = 2, visibility = "public")]
+    Asset(Asset),
+    #[entry_def(required_validations = 2, visibility = "public")]
+    AssetChunk(AssetChunk),
 }
 
 
@@ -35,9 +63,25 @@ pub enum PlaysetEntry {
 /// List of all link kinds handled by this Zome
 #[hdk_link_types]
 pub enum PlaysetLinkType {
-    All,
+    All, // Legacy listing links, kept readable for entries created before per-kind link types
     SvgMarkers,
     EmojiGroups,
     Spaces,
     Templates,
+    NameIndex,
+    Tagged,    // tag anchor -> piece
+    PieceTags, // piece -> tag anchor
+    TemplateSpaces, // template -> space using it as origin
+    MarkerSpaces,   // svg marker or emoji group -> space using it as marker
+    Assets,
+    SurfaceAssets, // template or space -> asset displayed by its surface
+    SubSpaces,    // parent space -> child space, tagged with the SpaceAttachment
+    ParentSpaces, // child space -> parent space, tagged with the SpaceAttachment
+    SpaceUpdates, // space -> its next revision
+}
+
+impl anchors::ListingLinkType for PlaysetLinkType {
+    fn legacy() -> Self {
+        PlaysetLinkType::All
+    }
 }
@@ -0,0 +1,34 @@
+use hdi::prelude::*;
+use holo_hash::EntryHashB64;
+
+/// Maximum size in bytes of the data held by one AssetChunk
+pub const ASSET_CHUNK_SIZE: usize = 256 * 1024;
+
+
+/// Asset Entry: manifest of binary data split into AssetChunk entries.
+/// Templates and spaces reference the assets of their surface with SurfaceAssets links, not by hash in the surface.
+#[hdk_entry_helper]
+#[derive(Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Asset {
+    pub name: String,
+    pub mime_type: String,
+    pub size: u64,
+    pub hash: String, // hex encoded sha256 of the whole data
+    pub chunks: Vec<EntryHashB64>,
+}
+
+
+/// AssetChunk Entry
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct AssetChunk {
+    pub data: Vec<u8>,
+}
+
+
+/// Hex encoded sha256 of some data
+pub fn asset_hash(data: &[u8]) -> ExternResult<String> {
+    let digest = hash_sha256(data.to_vec())?;
+    Ok(digest.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
@@ -2,11 +2,13 @@ mod template;
 mod emoji_group;
 mod svg_marker;
 mod space;
+mod asset;
 
 pub use template::*;
 pub use emoji_group::*;
 pub use svg_marker::*;
 pub use space::*;
+pub use asset::*;
 
 
 //--------------------------------------------------------------------------------------------------
@@ -4,6 +4,9 @@ use holo_hash::EntryHashB64;
 
 use crate::pieces::MarkerPiece;
 
+pub use space_types::{CoordinateSystem, SpaceAttachment, Zone, ZoneShape};
+
+
 /// Space entry definition
 #[hdk_entry_helper]
 #[derive(Clone)]
@@ -11,9 +14,11 @@ use crate::pieces::MarkerPiece;
 pub struct Space {
     pub name: String,
     pub origin: EntryHashB64,
-    //pub dimensionality: CoordinateSystem,
     pub surface: String, // Json
     pub maybe_marker_piece: Option<MarkerPiece>,
     pub meta: BTreeMap<String, String>,  // usable by the UI for whatever
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub zones: Vec<Zone>, // omitted when empty so entries created before zones keep their hash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maybe_coordinate_system: Option<CoordinateSystem>, // Cartesian when not set
 }
-
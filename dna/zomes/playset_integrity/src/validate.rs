@@ -0,0 +1,289 @@
+use hdi::prelude::*;
+use anchors::*;
+
+use crate::*;
+
+
+///
+#[hdk_extern]
+pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+  match op.flattened::<PlaysetEntry, PlaysetLinkType>()? {
+    FlatOp::StoreEntry(OpEntry::CreateEntry { app_entry, .. }) => {
+      match app_entry {
+        PlaysetEntry::Asset(asset) => validate_asset(asset),
+        PlaysetEntry::AssetChunk(chunk) => validate_asset_chunk(chunk),
+        _ => Ok(ValidateCallbackResult::Valid),
+      }
+    },
+    FlatOp::RegisterCreateLink { link_type, base_address, target_address, tag, .. } => {
+      validate_create_link(link_type, base_address, target_address, tag)
+    },
+    _ => Ok(ValidateCallbackResult::Valid),
+  }
+}
+
+
+/// The manifest must describe exactly the data held by its chunks
+fn validate_asset(asset: Asset) -> ExternResult<ValidateCallbackResult> {
+  let mut data = Vec::new();
+  for chunk_eh in asset.chunks {
+    let Some(mut chunk) = get_typed_entry::<AssetChunk>(&chunk_eh.into())?
+      else { return Ok(ValidateCallbackResult::Invalid("Asset chunk is not an AssetChunk entry".to_string())) };
+    data.append(&mut chunk.data);
+  }
+  if data.len() as u64 != asset.size {
+    return Ok(ValidateCallbackResult::Invalid(format!("Asset size is {} but its chunks hold {} bytes", asset.size, data.len())));
+  }
+  if asset_hash(&data)? != asset.hash {
+    return Ok(ValidateCallbackResult::Invalid("Asset hash does not match the data of its chunks".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+///
+fn validate_asset_chunk(chunk: AssetChunk) -> ExternResult<ValidateCallbackResult> {
+  if chunk.data.is_empty() || chunk.data.len() > ASSET_CHUNK_SIZE {
+    return Ok(ValidateCallbackResult::Invalid(format!("AssetChunk must hold between 1 and {} bytes", ASSET_CHUNK_SIZE)));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// Piece links must go from the shard anchor of the piece's name to an entry of that kind
+fn validate_create_link(link_type: PlaysetLinkType, base: AnyLinkableHash, target: AnyLinkableHash, tag: LinkTag) -> ExternResult<ValidateCallbackResult> {
+  let (root_path, type_name) = match link_type {
+    PlaysetLinkType::All => return Ok(ValidateCallbackResult::Valid),
+    PlaysetLinkType::NameIndex => return validate_name_index_link(base, target, tag),
+    PlaysetLinkType::Tagged => return validate_tagged_link(base, target, tag),
+    PlaysetLinkType::PieceTags => return validate_tag_link(target, base, tag),
+    PlaysetLinkType::TemplateSpaces => return validate_space_dependency_link(link_type, base, target),
+    PlaysetLinkType::MarkerSpaces => return validate_space_dependency_link(link_type, base, target),
+    PlaysetLinkType::SurfaceAssets => return validate_surface_asset_link(base, target),
+    PlaysetLinkType::SubSpaces => return validate_sub_space_link(base, target, tag),
+    PlaysetLinkType::ParentSpaces => return validate_sub_space_link(target, base, tag),
+    PlaysetLinkType::SpaceUpdates => return validate_space_update_link(base, target),
+    PlaysetLinkType::Templates => (TEMPLATES_ROOT_PATH, "Template"),
+    PlaysetLinkType::SvgMarkers => (SVG_MARKERS_ROOT_PATH, "SvgMarker"),
+    PlaysetLinkType::EmojiGroups => (EMOJI_GROUPS_ROOT_PATH, "EmojiGroup"),
+    PlaysetLinkType::Spaces => (SPACES_ROOT_PATH, "Space"),
+    PlaysetLinkType::Assets => (ASSETS_ROOT_PATH, "Asset"),
+  };
+  let anchor_path = AnchorPath::from(root_path);
+  let anchor: AnyLinkableHash = anchor_path.path_entry_hash()?.into();
+  /// Links created by `ensure()`: from the path root to the anchor, then from the anchor to a shard anchor
+  if target == anchor || base == anchor_root_hash()? {
+    if !anchor_path.is_ensure_link(&base, &target, &tag)? {
+      return Ok(ValidateCallbackResult::Invalid(format!("Only the path root can link to the {} anchor, tagged with its name", type_name)));
+    }
+    return Ok(ValidateCallbackResult::Valid);
+  }
+  if base == anchor {
+    return validate_shard_link(root_path, target, tag);
+  }
+  let Some(target_eh) = target.into_entry_hash()
+    else { return Ok(ValidateCallbackResult::Invalid(format!("{} link target must be an entry", type_name))) };
+  let Some(name) = get_piece_name(type_name, &target_eh)?
+    else { return Ok(ValidateCallbackResult::Invalid(format!("{} link target is not a {} entry", type_name, type_name))) };
+  let shard_anchor: AnyLinkableHash = shard_path(root_path, &name_shard(&name)).path_entry_hash()?.into();
+  if base != shard_anchor {
+    return Ok(ValidateCallbackResult::Invalid(format!("{} link base must be the shard anchor of the piece's name", type_name)));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// Links from a root anchor must go to the shard anchor named by their tag, as created by `ensure()`
+fn validate_shard_link(root_path: &str, target: AnyLinkableHash, tag: LinkTag) -> ExternResult<ValidateCallbackResult> {
+  let maybe_shard = AnchorComponent::from_tag(&tag)
+    .and_then(|component| String::try_from(&component).ok());
+  let Some(shard) = maybe_shard
+    else { return Ok(ValidateCallbackResult::Invalid("Link tag from an anchor must be a shard component".to_string())) };
+  if name_shard(&shard) != shard {
+    return Ok(ValidateCallbackResult::Invalid(format!("'{}' is not a shard", shard)));
+  }
+  let path = shard_path(root_path, &shard);
+  if target != path.path_entry_hash()?.into() || tag != path.make_tag()? {
+    return Ok(ValidateCallbackResult::Invalid(format!("Link from an anchor must go to the anchor of shard '{}'", shard)));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// Name index links are either the path links created by `ensure()` down to an index node,
+/// or links from the index node of a piece's name to that piece, tagged with the name.
+fn validate_name_index_link(base: AnyLinkableHash, target: AnyLinkableHash, tag: LinkTag) -> ExternResult<ValidateCallbackResult> {
+  let maybe_leaf = AnchorComponent::from_tag(&tag)
+    .and_then(|component| String::try_from(&component).ok());
+  if let Some(leaf) = maybe_leaf {
+    for type_name in INDEXED_PIECE_TYPES {
+      for path in name_index_paths_ending_with(NAME_INDEX_ROOT_PATH, type_name, &leaf) {
+        if path.is_ensure_link(&base, &target, &tag)? {
+          return Ok(ValidateCallbackResult::Valid);
+        }
+      }
+    }
+  }
+  let Ok(name) = String::from_utf8(tag.into_inner())
+    else { return Ok(ValidateCallbackResult::Invalid("NameIndex link tag must be a path component or a name".to_string())) };
+  for type_name in INDEXED_PIECE_TYPES {
+    let node: AnyLinkableHash = name_index_path(NAME_INDEX_ROOT_PATH, type_name, &normalize_name(&name)).path_entry_hash()?.into();
+    if base != node {
+      continue;
+    }
+    let Some(target_eh) = target.into_entry_hash()
+      else { return Ok(ValidateCallbackResult::Invalid("NameIndex link target must be an entry".to_string())) };
+    if get_piece_name(type_name, &target_eh)? != Some(name) {
+      return Ok(ValidateCallbackResult::Invalid(format!("NameIndex link target is not a {} with the tag's name", type_name)));
+    }
+    return Ok(ValidateCallbackResult::Valid);
+  }
+  Ok(ValidateCallbackResult::Invalid("NameIndex link base must be the index node of the tag's name".to_string()))
+}
+
+
+/// Tagged links are either the path links created by `ensure()` down to a tag anchor,
+/// or links from the anchor of a tag to a piece
+fn validate_tagged_link(base: AnyLinkableHash, target: AnyLinkableHash, tag: LinkTag) -> ExternResult<ValidateCallbackResult> {
+  let maybe_leaf = AnchorComponent::from_tag(&tag)
+    .and_then(|component| String::try_from(&component).ok());
+  if let Some(leaf) = maybe_leaf {
+    for path in tag_paths_ending_with(TAGS_ROOT_PATH, &leaf) {
+      if path.is_ensure_link(&base, &target, &tag)? {
+        return Ok(ValidateCallbackResult::Valid);
+      }
+    }
+  }
+  validate_tag_link(base, target, tag)
+}
+
+
+/// Tag links carry the tag as link tag and join the anchor of that tag and a piece
+fn validate_tag_link(anchor: AnyLinkableHash, piece: AnyLinkableHash, tag: LinkTag) -> ExternResult<ValidateCallbackResult> {
+  let Ok(tag) = String::from_utf8(tag.into_inner())
+    else { return Ok(ValidateCallbackResult::Invalid("Tag link tag must be a string".to_string())) };
+  let tag_anchor: AnyLinkableHash = tag_path(TAGS_ROOT_PATH, &tag).path_entry_hash()?.into();
+  if anchor != tag_anchor {
+    return Ok(ValidateCallbackResult::Invalid(format!("Tag link must be attached to the anchor of tag '{}'", tag)));
+  }
+  let Some(piece_eh) = piece.into_entry_hash()
+    else { return Ok(ValidateCallbackResult::Invalid("Tagged piece must be an entry".to_string())) };
+  if get_any_piece_name(&piece_eh)?.is_none() {
+    return Ok(ValidateCallbackResult::Invalid("Tagged entry is not a piece".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// Reverse dependency links must go from the template or marker a space references to that space
+fn validate_space_dependency_link(link_type: PlaysetLinkType, base: AnyLinkableHash, target: AnyLinkableHash) -> ExternResult<ValidateCallbackResult> {
+  let Some(space_eh) = target.into_entry_hash()
+    else { return Ok(ValidateCallbackResult::Invalid("Space dependency link target must be an entry".to_string())) };
+  let Some(space) = get_typed_entry::<Space>(&space_eh)?
+    else { return Ok(ValidateCallbackResult::Invalid("Space dependency link target is not a Space entry".to_string())) };
+  let maybe_dependency: Option<EntryHash> = match link_type {
+    PlaysetLinkType::TemplateSpaces => Some(space.origin.into()),
+    _ => space.maybe_marker_piece.map(|marker| marker.eh()),
+  };
+  if maybe_dependency.map(AnyLinkableHash::from) != Some(base) {
+    return Ok(ValidateCallbackResult::Invalid("Space dependency link base must be referenced by the target space".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// Surface asset links must go from a template or space to an asset
+fn validate_surface_asset_link(base: AnyLinkableHash, target: AnyLinkableHash) -> ExternResult<ValidateCallbackResult> {
+  let (Some(piece_eh), Some(asset_eh)) = (base.into_entry_hash(), target.into_entry_hash())
+    else { return Ok(ValidateCallbackResult::Invalid("SurfaceAssets link must join two entries".to_string())) };
+  if get_piece_name("Template", &piece_eh)?.is_none() && get_piece_name("Space", &piece_eh)?.is_none() {
+    return Ok(ValidateCallbackResult::Invalid("SurfaceAssets link base must be a Template or a Space".to_string()));
+  }
+  if get_piece_name("Asset", &asset_eh)?.is_none() {
+    return Ok(ValidateCallbackResult::Invalid("SurfaceAssets link target must be an Asset".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// Sub-space links join two different spaces, attached to the whole parent, one of its zones or a point
+fn validate_sub_space_link(parent: AnyLinkableHash, child: AnyLinkableHash, tag: LinkTag) -> ExternResult<ValidateCallbackResult> {
+  if parent == child {
+    return Ok(ValidateCallbackResult::Invalid("A space cannot be its own sub-space".to_string()));
+  }
+  let (Some(parent_eh), Some(child_eh)) = (parent.into_entry_hash(), child.into_entry_hash())
+    else { return Ok(ValidateCallbackResult::Invalid("Sub-space link must join two entries".to_string())) };
+  let Some(parent_space) = get_typed_entry::<Space>(&parent_eh)?
+    else { return Ok(ValidateCallbackResult::Invalid("Sub-space parent is not a Space".to_string())) };
+  if get_typed_entry::<Space>(&child_eh)?.is_none() {
+    return Ok(ValidateCallbackResult::Invalid("Sub-space child is not a Space".to_string()));
+  }
+  let Some(attachment) = SpaceAttachment::from_tag(tag)
+    else { return Ok(ValidateCallbackResult::Invalid("Sub-space link tag must be a SpaceAttachment".to_string())) };
+  if let SpaceAttachment::Zone(zone_name) = attachment {
+    if !parent_space.zones.iter().any(|zone| zone.name == zone_name) {
+      return Ok(ValidateCallbackResult::Invalid(format!("Parent space has no zone '{}'", zone_name)));
+    }
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// Space update links join a space to a different space, its next revision
+fn validate_space_update_link(base: AnyLinkableHash, target: AnyLinkableHash) -> ExternResult<ValidateCallbackResult> {
+  if base == target {
+    return Ok(ValidateCallbackResult::Invalid("A space cannot be its own revision".to_string()));
+  }
+  let (Some(old_eh), Some(new_eh)) = (base.into_entry_hash(), target.into_entry_hash())
+    else { return Ok(ValidateCallbackResult::Invalid("SpaceUpdates link must join two entries".to_string())) };
+  if get_piece_name("Space", &old_eh)?.is_none() || get_piece_name("Space", &new_eh)?.is_none() {
+    return Ok(ValidateCallbackResult::Invalid("SpaceUpdates link must join two Spaces".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// Name of the piece at `eh` whatever its type
+pub fn get_any_piece_name(eh: &EntryHash) -> ExternResult<Option<String>> {
+  for type_name in ["Template", "SvgMarker", "EmojiGroup", "Space", "Asset"] {
+    if let Some(name) = get_piece_name(type_name, eh)? {
+      return Ok(Some(name));
+    }
+  }
+  Ok(None)
+}
+
+
+/// Name of the piece at `eh` if it is a piece of the given type name
+pub fn get_piece_name(type_name: &str, eh: &EntryHash) -> ExternResult<Option<String>> {
+  let maybe_name = match type_name {
+    "Template" => get_typed_entry::<Template>(eh)?.map(|piece| piece.name),
+    "SvgMarker" => get_typed_entry::<SvgMarker>(eh)?.map(|piece| piece.name),
+    "EmojiGroup" => get_typed_entry::<EmojiGroup>(eh)?.map(|piece| piece.name),
+    "Space" => get_typed_entry::<Space>(eh)?.map(|piece| piece.name),
+    "Asset" => get_typed_entry::<Asset>(eh)?.map(|piece| piece.name),
+    _ => None,
+  };
+  Ok(maybe_name)
+}
+
+
+/// The entry must deserialize into T and hash back to the same hash,
+/// otherwise a piece with a superset of T's fields would pass as a T.
+fn get_typed_entry<T>(eh: &EntryHash) -> ExternResult<Option<T>>
+  where
+    T: Clone + TryFrom<Entry, Error = WasmError>,
+    Entry: TryFrom<T, Error = WasmError>,
+{
+  let entry = must_get_entry(eh.clone())?.into_content();
+  let Ok(typed) = T::try_from(entry)
+    else { return Ok(None) };
+  if hash_entry(typed.clone())? != *eh {
+    return Ok(None);
+  }
+  Ok(Some(typed))
+}
+
+
+#[cfg(test)]
+mod tests;
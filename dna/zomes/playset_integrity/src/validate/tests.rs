@@ -0,0 +1,187 @@
+use anchors::test_utils::*;
+
+use super::*;
+
+
+fn add_template(name: &str) -> AnyLinkableHash {
+  add_test_entry(Template {name: name.to_string(), surface: "{}".to_string()}).into()
+}
+
+fn hash_of(path: &AnchorPath) -> AnyLinkableHash {
+  path.path_entry_hash().unwrap().into()
+}
+
+fn is_valid(result: ExternResult<ValidateCallbackResult>) -> bool {
+  matches!(result.unwrap(), ValidateCallbackResult::Valid)
+}
+
+
+#[test]
+fn piece_link_must_come_from_the_shard_of_its_name() {
+  TestHdi::install();
+  let template = add_template("Forest");
+  let shard_f = hash_of(&shard_path(TEMPLATES_ROOT_PATH, "f"));
+  let shard_g = hash_of(&shard_path(TEMPLATES_ROOT_PATH, "g"));
+  assert!(is_valid(validate_create_link(PlaysetLinkType::Templates, shard_f.clone(), template.clone(), LinkTag::from(()))));
+  assert!(!is_valid(validate_create_link(PlaysetLinkType::Templates, shard_g, template, LinkTag::from(()))));
+  // The target must be a piece of the link's kind
+  let not_a_template: AnyLinkableHash = add_test_entry(SvgMarker {name: "Flag".to_string(), value: "{}".to_string()}).into();
+  assert!(!is_valid(validate_create_link(PlaysetLinkType::Templates, shard_f, not_a_template, LinkTag::from(()))));
+}
+
+
+#[test]
+fn ensure_links_must_follow_the_shard_path() {
+  TestHdi::install();
+  let anchor = AnchorPath::from(TEMPLATES_ROOT_PATH);
+  let shard = shard_path(TEMPLATES_ROOT_PATH, "f");
+  // Root -> anchor, tagged with the anchor's name
+  let root = anchor_root_hash().unwrap();
+  assert!(is_valid(validate_create_link(PlaysetLinkType::Templates, root.clone(), hash_of(&anchor), anchor.make_tag().unwrap())));
+  assert!(!is_valid(validate_create_link(PlaysetLinkType::Templates, root, hash_of(&anchor), shard.make_tag().unwrap())));
+  // Anchor -> shard, tagged with the shard key
+  assert!(is_valid(validate_create_link(PlaysetLinkType::Templates, hash_of(&anchor), hash_of(&shard), shard.make_tag().unwrap())));
+  let other_shard = shard_path(TEMPLATES_ROOT_PATH, "g");
+  assert!(!is_valid(validate_create_link(PlaysetLinkType::Templates, hash_of(&anchor), hash_of(&other_shard), shard.make_tag().unwrap())));
+  // Shard keys are single lowercase characters
+  let not_a_shard = shard_path(TEMPLATES_ROOT_PATH, "Fo");
+  assert!(!is_valid(validate_create_link(PlaysetLinkType::Templates, hash_of(&anchor), hash_of(&not_a_shard), not_a_shard.make_tag().unwrap())));
+}
+
+
+#[test]
+fn name_index_accepts_ensure_links_down_to_a_node() {
+  TestHdi::install();
+  let root = AnchorPath::from(NAME_INDEX_ROOT_PATH);
+  let kind = name_index_path(NAME_INDEX_ROOT_PATH, "Template", "");
+  let level_1 = name_index_path(NAME_INDEX_ROOT_PATH, "Template", "f");
+  let level_2 = name_index_path(NAME_INDEX_ROOT_PATH, "Template", "fo");
+  let anchor_root = anchor_root_hash().unwrap();
+  assert!(is_valid(validate_name_index_link(anchor_root, hash_of(&root), root.make_tag().unwrap())));
+  assert!(is_valid(validate_name_index_link(hash_of(&root), hash_of(&kind), kind.make_tag().unwrap())));
+  assert!(is_valid(validate_name_index_link(hash_of(&kind), hash_of(&level_1), level_1.make_tag().unwrap())));
+  assert!(is_valid(validate_name_index_link(hash_of(&level_1), hash_of(&level_2), level_2.make_tag().unwrap())));
+  // The target must be the child of the base named by the tag
+  let other = name_index_path(NAME_INDEX_ROOT_PATH, "Template", "fi");
+  assert!(!is_valid(validate_name_index_link(hash_of(&level_1), hash_of(&other), level_2.make_tag().unwrap())));
+  assert!(!is_valid(validate_name_index_link(hash_of(&kind), hash_of(&level_2), level_2.make_tag().unwrap())));
+  // Nodes stop at NAME_INDEX_DEPTH characters
+  let too_deep = level_2.child(AnchorComponent::from("fore")).child(AnchorComponent::from("fores"));
+  let parent = too_deep.parent().unwrap();
+  assert!(!is_valid(validate_name_index_link(hash_of(&parent), hash_of(&too_deep), too_deep.make_tag().unwrap())));
+}
+
+
+#[test]
+fn name_index_links_a_node_to_the_pieces_named_by_it() {
+  TestHdi::install();
+  let template = add_template("Forest");
+  let node = name_index_path(NAME_INDEX_ROOT_PATH, "Template", "forest");
+  let name_tag = LinkTag::new("Forest".as_bytes().to_vec());
+  assert!(is_valid(validate_name_index_link(hash_of(&node), template.clone(), name_tag.clone())));
+  // The base must be the node of the tag's name
+  let other_node = name_index_path(NAME_INDEX_ROOT_PATH, "Template", "fox");
+  assert!(!is_valid(validate_name_index_link(hash_of(&other_node), template.clone(), name_tag)));
+  // The target must have the tag's name
+  let other_tag = LinkTag::new("Forge".as_bytes().to_vec());
+  assert!(!is_valid(validate_name_index_link(hash_of(&node), template.clone(), other_tag)));
+  // Tags are a path component or a name
+  assert!(!is_valid(validate_name_index_link(hash_of(&node), template, LinkTag::new(vec![0xff, 0xfe]))));
+}
+
+
+#[test]
+fn tagged_links_are_ensure_links_or_go_from_a_tag_anchor_to_a_piece() {
+  TestHdi::install();
+  let template = add_template("Forest");
+  let root = AnchorPath::from(TAGS_ROOT_PATH);
+  let anchor = tag_path(TAGS_ROOT_PATH, "Woods");
+  assert!(is_valid(validate_tagged_link(anchor_root_hash().unwrap(), hash_of(&root), root.make_tag().unwrap())));
+  assert!(is_valid(validate_tagged_link(hash_of(&root), hash_of(&anchor), anchor.make_tag().unwrap())));
+  let woods = LinkTag::new("woods".as_bytes().to_vec());
+  assert!(is_valid(validate_tagged_link(hash_of(&anchor), template.clone(), woods.clone())));
+  assert!(is_valid(validate_tag_link(hash_of(&anchor), template.clone(), woods.clone())));
+  // The root anchor only links to tag anchors, named by the link tag
+  assert!(!is_valid(validate_tagged_link(hash_of(&root), template.clone(), woods.clone())));
+  let other = tag_path(TAGS_ROOT_PATH, "lakes");
+  assert!(!is_valid(validate_tagged_link(hash_of(&root), hash_of(&other), anchor.make_tag().unwrap())));
+  // A tag anchor only links to pieces, tagged with its tag
+  assert!(!is_valid(validate_tagged_link(hash_of(&other), template.clone(), woods)));
+  let not_a_piece: AnyLinkableHash = add_test_entry(AssetChunk {data: vec![1]}).into();
+  assert!(!is_valid(validate_tagged_link(hash_of(&anchor), not_a_piece, LinkTag::new("woods".as_bytes().to_vec()))));
+}
+
+
+fn add_asset(data: &[u8], chunk_size: usize) -> Asset {
+  let chunks = data.chunks(chunk_size)
+    .map(|chunk| add_test_entry(AssetChunk {data: chunk.to_vec()}).into())
+    .collect();
+  Asset {
+    name: "map.png".to_string(),
+    mime_type: "image/png".to_string(),
+    size: data.len() as u64,
+    hash: asset_hash(data).unwrap(),
+    chunks,
+  }
+}
+
+
+#[test]
+fn asset_manifest_must_describe_the_data_of_its_chunks() {
+  TestHdi::install();
+  let data: Vec<u8> = (0..100).collect();
+  assert!(is_valid(validate_asset(add_asset(&data, 30))));
+  let mut wrong_size = add_asset(&data, 30);
+  wrong_size.size += 1;
+  assert!(!is_valid(validate_asset(wrong_size)));
+  let mut wrong_hash = add_asset(&data, 30);
+  wrong_hash.hash = asset_hash(&data[1..]).unwrap();
+  assert!(!is_valid(validate_asset(wrong_hash)));
+  let mut reordered = add_asset(&data, 30);
+  reordered.chunks.reverse();
+  assert!(!is_valid(validate_asset(reordered)));
+}
+
+
+#[test]
+fn asset_chunk_size_is_bounded() {
+  TestHdi::install();
+  assert!(is_valid(validate_asset_chunk(AssetChunk {data: vec![0; ASSET_CHUNK_SIZE]})));
+  assert!(!is_valid(validate_asset_chunk(AssetChunk {data: vec![0; ASSET_CHUNK_SIZE + 1]})));
+  assert!(!is_valid(validate_asset_chunk(AssetChunk {data: Vec::new()})));
+}
+
+
+fn add_space(name: &str, zones: Vec<Zone>) -> AnyLinkableHash {
+  let origin = add_test_entry(Template {name: format!("{} template", name), surface: "{}".to_string()});
+  add_test_entry(Space {
+    name: name.to_string(),
+    origin: origin.into(),
+    surface: "{}".to_string(),
+    maybe_marker_piece: None,
+    meta: Default::default(),
+    zones,
+    maybe_coordinate_system: None,
+  }).into()
+}
+
+
+#[test]
+fn sub_space_links_join_two_spaces_on_an_existing_zone() {
+  TestHdi::install();
+  let kitchen = Zone {name: "Kitchen".to_string(), shape: ZoneShape::Rect {x: 0.0, y: 0.0, width: 10.0, height: 10.0}};
+  let house = add_space("House", vec![kitchen]);
+  let fridge = add_space("Fridge", Vec::new());
+  let whole = SpaceAttachment::Whole.to_tag().unwrap();
+  assert!(is_valid(validate_sub_space_link(house.clone(), fridge.clone(), whole.clone())));
+  assert!(is_valid(validate_sub_space_link(house.clone(), fridge.clone(), SpaceAttachment::Point {x: 1.0, y: 2.0}.to_tag().unwrap())));
+  assert!(is_valid(validate_sub_space_link(house.clone(), fridge.clone(), SpaceAttachment::Zone("Kitchen".to_string()).to_tag().unwrap())));
+  // The zone must be one of the parent's
+  assert!(!is_valid(validate_sub_space_link(house.clone(), fridge.clone(), SpaceAttachment::Zone("Attic".to_string()).to_tag().unwrap())));
+  assert!(!is_valid(validate_sub_space_link(fridge.clone(), house.clone(), SpaceAttachment::Zone("Kitchen".to_string()).to_tag().unwrap())));
+  // A space is not its own sub-space, and both ends are spaces
+  assert!(!is_valid(validate_sub_space_link(house.clone(), house.clone(), whole.clone())));
+  assert!(!is_valid(validate_sub_space_link(house.clone(), add_template("Forest"), whole)));
+  // The tag is a SpaceAttachment
+  assert!(!is_valid(validate_sub_space_link(house, fridge, LinkTag::new(vec![0xff]))));
+}
@@ -0,0 +1,93 @@
+use hdk::prelude::*;
+use holo_hash::ActionHashB64;
+
+use where_integrity::*;
+use crate::{
+  here::*,
+  migrate::*,
+};
+
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HereAttachmentInput {
+  /// Session link of any version of the Here
+  pub here_link_ah: ActionHashB64,
+  pub hrl_with_context: HrlWithContext,
+}
+
+
+/// Attach a record of any happ to a Here, keyed on the Here's identity so that it follows its updates.
+/// Links both ways so that the Heres referencing an HRL can be found.
+#[hdk_extern]
+fn add_here_attachment(input: HereAttachmentInput) -> ExternResult<()> {
+  let here_id = get_here_id(input.here_link_ah.into())?;
+  let anchor_eh = hrl_anchor_hash(&input.hrl_with_context.hrl)?;
+  let already = get_links(here_id.clone(), WhereLinkType::HereAttachments, None)?.into_iter()
+    .filter_map(|link| HrlWithContext::from_tag(link.tag))
+    .any(|attachment| attachment == input.hrl_with_context);
+  if already {
+    return Ok(());
+  }
+  let tag = input.hrl_with_context.to_tag()?;
+  create_link(here_id.clone(), anchor_eh.clone(), WhereLinkType::HereAttachments, tag.clone())?;
+  create_link(anchor_eh, here_id, WhereLinkType::AttachedHeres, tag)?;
+  Ok(())
+}
+
+
+/// Remove the attachments of an HRL we made on a Here, whatever their context
+#[hdk_extern]
+fn remove_here_attachment(input: HereAttachmentInput) -> ExternResult<()> {
+  let here_id = get_here_id(input.here_link_ah.into())?;
+  let hrl = input.hrl_with_context.hrl;
+  let anchor_eh = hrl_anchor_hash(&hrl)?;
+  let me = agent_info()?.agent_latest_pubkey;
+  let is_mine = |link: &Link| link.author == me
+    && HrlWithContext::from_tag(link.tag.clone()).map(|attachment| attachment.hrl == hrl).unwrap_or(false);
+  for link in get_links(here_id.clone(), WhereLinkType::HereAttachments, None)? {
+    if is_mine(&link) {
+      delete_link(link.create_link_hash)?;
+    }
+  }
+  for link in get_links(anchor_eh, WhereLinkType::AttachedHeres, None)? {
+    if link.target == here_id.clone().into() && is_mine(&link) {
+      delete_link(link.create_link_hash)?;
+    }
+  }
+  Ok(())
+}
+
+
+/// HRLs attached to a Here
+#[hdk_extern]
+fn get_here_attachments(here_link_ah: ActionHashB64) -> ExternResult<Vec<HrlWithContext>> {
+  let here_id = get_here_id(here_link_ah.into())?;
+  let attachments = get_links(here_id, WhereLinkType::HereAttachments, None)?.into_iter()
+    .filter_map(|link| HrlWithContext::from_tag(link.tag))
+    .collect();
+  Ok(attachments)
+}
+
+
+/// Current version of the Heres, across all sessions, to which an HRL is attached.
+/// Heres that were deleted or have expired are omitted.
+#[hdk_extern]
+fn get_heres_attached_to(hrl: Hrl) -> ExternResult<Vec<HereOutput>> {
+  let mut heres: Vec<HereOutput> = Vec::new();
+  let mut here_ids: Vec<ActionHash> = Vec::new();
+  for link in get_links(hrl_anchor_hash(&hrl)?, WhereLinkType::AttachedHeres, None)? {
+    let Some(here_id) = link.target.into_action_hash()
+      else {continue};
+    if here_ids.contains(&here_id) {
+      continue;
+    }
+    here_ids.push(here_id.clone());
+    let Some(mut here) = get_current_here_output(here_id)?
+      else {continue};
+    apply_session_transform(here.entry.session_eh.clone(), std::slice::from_mut(&mut here))?;
+    heres.push(here);
+  }
+  Ok(heres)
+}
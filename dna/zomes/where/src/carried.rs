@@ -0,0 +1,91 @@
+use hdk::prelude::*;
+use holo_hash::{EntryHashB64, AgentPubKeyB64, ActionHashB64};
+use zome_utils::*;
+
+use where_integrity::*;
+use crate::{
+  here::*,
+  migrate::*,
+  space::*,
+};
+
+
+/// A Here of a previous session offered to its author in a new session
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CarriedHereOutput {
+  pub carried_ah: ActionHashB64,
+  pub author: AgentPubKeyB64,
+  pub here: Here,
+}
+
+
+/// Link the session link of the latest Here of each agent in the previous session to the new session.
+/// Validation checks the tag against the author of that session link.
+/// The previous session's transform is applied when reading.
+pub fn carry_forward_heres(previous_session_eh: EntryHash, session_eh: EntryHash) -> ExternResult<()> {
+  for here in get_latest_heres_per_agent(previous_session_eh)? {
+    let author: AgentPubKey = here.author.into();
+    create_link(session_eh.clone(), ActionHash::from(here.link_ah), WhereLinkType::Carried, LinkTag::new(author.get_raw_39().to_vec()))?;
+  }
+  Ok(())
+}
+
+
+/// Heres carried into a session and not yet confirmed or discarded
+#[hdk_extern]
+fn get_carried_heres(session_eh: EntryHashB64) -> ExternResult<Vec<CarriedHereOutput>> {
+  let links = get_links(session_eh, WhereLinkType::Carried, None)?;
+  let mut output = Vec::with_capacity(links.len());
+  for link in links {
+    let Ok(author) = AgentPubKey::from_raw_39(link.tag.into_inner())
+      else {continue};
+    let Some(here_link_ah) = link.target.into_action_hash()
+      else {continue};
+    let Ok(mut here) = get_here_of_link(&here_link_ah)
+      else {continue};
+    apply_here_session_transform(&mut here)?;
+    output.push(CarriedHereOutput {carried_ah: link.create_link_hash.into(), author: author.into(), here});
+  }
+  Ok(output)
+}
+
+
+/// Place the carried Here in its new session, as is
+#[hdk_extern]
+fn confirm_carried_here(carried_ah: ActionHashB64) -> ExternResult<ActionHashB64> {
+  let (session_eh, here) = get_my_carried_here(carried_ah.clone())?;
+  let system = get_session_space_info(session_eh.clone().into())?.coordinate_system();
+  let here = Here {session_eh: session_eh.into(), maybe_expires_at: None, maybe_origin_ah: None, ..here};
+  let link_ah = create_here(here, system)?;
+  delete_link(carried_ah.into())?;
+  Ok(link_ah)
+}
+
+
+/// Drop the carried Here without placing it
+#[hdk_extern]
+fn discard_carried_here(carried_ah: ActionHashB64) -> ExternResult<()> {
+  let _ = get_my_carried_here(carried_ah.clone())?;
+  delete_link(carried_ah.into())?;
+  Ok(())
+}
+
+
+/// Get the session and Here of a carried link, making sure the Here is ours
+fn get_my_carried_here(carried_ah: ActionHashB64) -> ExternResult<(EntryHash, Here)> {
+  let Some(record) = get(ActionHash::from(carried_ah), GetOptions::content())?
+    else { return zome_error!("Carried Here not found") };
+  let Action::CreateLink(create) = record.action()
+    else { return zome_error!("Not a carried Here") };
+  let me = agent_info()?.agent_latest_pubkey;
+  if create.tag.clone().into_inner() != me.get_raw_39().to_vec() {
+    return zome_error!("Carried Here belongs to another agent");
+  }
+  let session_eh = create.base_address.clone().into_entry_hash().unwrap();
+  let Some(here_link_ah) = create.target_address.clone().into_action_hash()
+    else { return zome_error!("Not a carried Here") };
+  let mut here = get_here_of_link(&here_link_ah)?;
+  apply_here_session_transform(&mut here)?;
+  Ok((session_eh, here))
+}
@@ -0,0 +1,138 @@
+use hdk::prelude::*;
+use holo_hash::{AgentPubKeyB64, ActionHashB64};
+use zome_utils::*;
+
+use where_integrity::*;
+use crate::here::*;
+
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddHereCommentInput {
+  /// Session link of any version of the Here
+  pub here_link_ah: ActionHashB64,
+  pub text: String,
+  #[serde(default)]
+  pub maybe_reply_to: Option<ActionHashB64>,
+}
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditHereCommentInput {
+  pub comment_ah: ActionHashB64,
+  pub text: String,
+}
+
+/// Latest version of a comment
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentOutput {
+  /// ActionHash of the comment's creation, its identity across edits
+  pub comment_ah: ActionHashB64,
+  pub comment: HereComment,
+  pub author: AgentPubKeyB64,
+  pub created_at: Timestamp,
+  pub maybe_edited_at: Option<Timestamp>,
+}
+
+
+/// Comment on a Here, keyed on its identity so that it follows the Here's updates
+#[hdk_extern]
+fn add_here_comment(input: AddHereCommentInput) -> ExternResult<CommentOutput> {
+  let here_id = get_here_id(input.here_link_ah.into())?;
+  let comment = HereComment {
+    here_link_ah: here_id.clone().into(),
+    text: input.text,
+    maybe_reply_to: input.maybe_reply_to,
+  };
+  let comment_ah = create_entry(WhereEntry::HereComment(comment))?;
+  create_link(here_id, comment_ah.clone(), WhereLinkType::Comments, LinkTag::from(()))?;
+  let Some(output) = get_comment_output(comment_ah)?
+    else { return zome_error!("Comment not found after creation") };
+  Ok(output)
+}
+
+
+/// Change the text of one of our comments
+#[hdk_extern]
+fn edit_here_comment(input: EditHereCommentInput) -> ExternResult<CommentOutput> {
+  let comment_ah: ActionHash = input.comment_ah.into();
+  let Some(current) = get_comment_output(comment_ah.clone())?
+    else { return zome_error!("Comment not found") };
+  if AgentPubKey::from(current.author.clone()) != agent_info()?.agent_latest_pubkey {
+    return zome_error!("Only the author can edit a comment");
+  }
+  let comment = HereComment {text: input.text, ..current.comment};
+  update_entry(comment_ah.clone(), WhereEntry::HereComment(comment))?;
+  let Some(output) = get_comment_output(comment_ah)?
+    else { return zome_error!("Comment not found after edit") };
+  Ok(output)
+}
+
+
+/// Delete one of our comments
+#[hdk_extern]
+fn delete_here_comment(comment_ah: ActionHashB64) -> ExternResult<()> {
+  let comment_ah: ActionHash = comment_ah.into();
+  let Some(current) = get_comment_output(comment_ah.clone())?
+    else { return zome_error!("Comment not found") };
+  if AgentPubKey::from(current.author.clone()) != agent_info()?.agent_latest_pubkey {
+    return zome_error!("Only the author can delete a comment");
+  }
+  let target = AnyLinkableHash::from(comment_ah.clone());
+  for link in get_links(ActionHash::from(current.comment.here_link_ah), WhereLinkType::Comments, None)? {
+    if link.target == target {
+      delete_link(link.create_link_hash)?;
+    }
+  }
+  delete_entry(comment_ah)?;
+  Ok(())
+}
+
+
+/// Comments on a Here, given the session link of any of its versions, oldest first
+#[hdk_extern]
+fn get_here_comments(here_link_ah: ActionHashB64) -> ExternResult<Vec<CommentOutput>> {
+  let here_id = get_here_id(here_link_ah.into())?;
+  let links = get_links(here_id, WhereLinkType::Comments, None)?;
+  let mut comments = Vec::with_capacity(links.len());
+  for link in links {
+    let Some(comment_ah) = link.target.into_action_hash()
+      else {continue};
+    let Some(output) = get_comment_output(comment_ah)?
+      else {continue};
+    comments.push(output);
+  }
+  comments.sort_by_key(|comment| comment.created_at);
+  Ok(comments)
+}
+
+
+/// Latest version of a comment, or None if it was deleted
+fn get_comment_output(comment_ah: ActionHash) -> ExternResult<Option<CommentOutput>> {
+  let Some(Details::Record(details)) = get_details(comment_ah.clone(), GetOptions::latest())?
+    else { return Ok(None) };
+  if !details.deletes.is_empty() {
+    return Ok(None);
+  }
+  let created = details.record.action().clone();
+  let mut record = details.record;
+  let maybe_latest_update = details.updates.into_iter()
+    .max_by_key(|update| update.action().timestamp());
+  let maybe_edited_at = maybe_latest_update.as_ref().map(|update| update.action().timestamp());
+  if let Some(update) = maybe_latest_update {
+    let Some(update_record) = get(update.as_hash().clone(), GetOptions::latest())?
+      else { return Ok(None) };
+    record = update_record;
+  }
+  let comment = get_typed_from_record::<HereComment>(record)?;
+  Ok(Some(CommentOutput {
+    comment_ah: comment_ah.into(),
+    comment,
+    author: created.author().clone().into(),
+    created_at: created.timestamp(),
+    maybe_edited_at,
+  }))
+}
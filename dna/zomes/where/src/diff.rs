@@ -0,0 +1,83 @@
+use hdk::prelude::*;
+use holo_hash::{EntryHashB64, AgentPubKeyB64};
+use zome_utils::*;
+
+use crate::{
+  geometry::*,
+  here::*,
+  migrate::*,
+  placement_session::*,
+  space::*,
+  stats::*,
+};
+
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDiffInput {
+  pub from_session_eh: EntryHashB64,
+  pub to_session_eh: EntryHashB64,
+}
+
+/// Latest Heres of an agent present in both sessions
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentMove {
+  pub agent: AgentPubKeyB64,
+  pub from: HereOutput,
+  pub to: HereOutput,
+  /// Distance between the two positions, in km on geo spaces
+  pub maybe_displacement: Option<f64>,
+  pub tag_changed: bool,
+  pub emoji_changed: bool,
+}
+
+/// Per-agent changes between two sessions of a space
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDiff {
+  pub moves: Vec<AgentMove>,
+  /// Latest Heres of agents only in the second session
+  pub joined: Vec<HereOutput>,
+  /// Latest Heres of agents only in the first session
+  pub left: Vec<HereOutput>,
+}
+
+
+/// Compare the latest Here of each agent between two sessions of the same space
+#[hdk_extern]
+fn get_session_diff(input: SessionDiffInput) -> ExternResult<SessionDiff> {
+  let Some(from_session) = get_session_from_eh(input.from_session_eh.clone())?
+    else { return zome_error!("Session not found: {}", input.from_session_eh) };
+  let Some(to_session) = get_session_from_eh(input.to_session_eh.clone())?
+    else { return zome_error!("Session not found: {}", input.to_session_eh) };
+  if from_session.space_eh != to_session.space_eh {
+    return zome_error!("Sessions are not of the same space");
+  }
+  let system = get_space_info(to_session.space_eh)?.coordinate_system();
+  let mut from_heres = get_latest_heres_per_agent(input.from_session_eh.clone().into())?;
+  let mut to_heres = get_latest_heres_per_agent(input.to_session_eh.clone().into())?;
+  apply_session_transform(input.from_session_eh, &mut from_heres)?;
+  apply_session_transform(input.to_session_eh, &mut to_heres)?;
+  let mut moves = Vec::new();
+  let mut left = Vec::new();
+  for from in from_heres {
+    let Some(index) = to_heres.iter().position(|to| to.author == from.author)
+      else { left.push(from); continue };
+    let to = to_heres.remove(index);
+    let maybe_displacement = match (parse_position(&from.entry.value, system), parse_position(&to.entry.value, system)) {
+      (Some(a), Some(b)) => Some(distance(system, a, b)),
+      _ => None,
+    };
+    moves.push(AgentMove {
+      agent: from.author.clone(),
+      maybe_displacement,
+      tag_changed: get_meta_string(&from.entry, TAG_META_KEY) != get_meta_string(&to.entry, TAG_META_KEY),
+      emoji_changed: get_meta_string(&from.entry, EMOJI_META_KEY) != get_meta_string(&to.entry, EMOJI_META_KEY),
+      from,
+      to,
+    });
+  }
+  Ok(SessionDiff {moves, joined: to_heres, left})
+}
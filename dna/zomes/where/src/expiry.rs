@@ -0,0 +1,61 @@
+use hdk::prelude::*;
+
+use where_integrity::*;
+use crate::{
+  here::*,
+  snapshot::*,
+};
+
+
+/// How often online agents delete their expired Heres
+pub const EXPIRED_HERES_CRON: &'static str = "0 */15 * * * * *";
+
+
+/// Scheduled callback: delete the session links of our Heres that have expired
+#[hdk_extern(infallible)]
+fn delete_expired_heres(_: Option<Schedule>) -> Option<Schedule> {
+  if let Err(e) = delete_my_expired_heres() {
+    debug!("delete_expired_heres() failed: {:?}", e);
+  }
+  Some(Schedule::Persisted(EXPIRED_HERES_CRON.to_string()))
+}
+
+
+/// Delete the session links of our expired Heres, found from our Expiring links.
+/// The Expiring link is deleted once its Here is, so each run only visits Heres not expired yet.
+fn delete_my_expired_heres() -> ExternResult<()> {
+  let now = sys_time()?;
+  let me = agent_info()?.agent_latest_pubkey;
+  for link in get_links(me, WhereLinkType::Expiring, None)? {
+    if !expiry_from_tag(&link.tag).map(|expires_at| expires_at <= now).unwrap_or(false) {
+      continue;
+    }
+    let Some(link_ah) = link.target.into_action_hash()
+      else {continue};
+    if let Err(e) = expire_here(link_ah.clone()) {
+      debug!("delete_expired_heres(): Failed to delete Here {}: {:?}", link_ah, e);
+      continue;
+    }
+    delete_link(link.create_link_hash)?;
+  }
+  Ok(())
+}
+
+
+/// Delete the session link of an expired Here, unless it is already deleted.
+/// Heres of closed sessions are kept in their snapshot.
+fn expire_here(link_ah: ActionHash) -> ExternResult<()> {
+  let Some(Details::Record(details)) = get_details(link_ah.clone(), GetOptions::content())?
+    else { return Ok(()) };
+  if !details.deletes.is_empty() {
+    return Ok(());
+  }
+  let Action::CreateLink(create) = details.record.action()
+    else { return Ok(()) };
+  let Some(session_eh) = create.base_address.clone().into_entry_hash()
+    else { return Ok(()) };
+  if is_session_closed(session_eh)? {
+    return Ok(());
+  }
+  delete_here(link_ah.into())
+}
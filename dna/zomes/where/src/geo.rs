@@ -0,0 +1,82 @@
+use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use zome_utils::*;
+
+use where_integrity::*;
+use crate::{
+  geometry::*,
+  here::*,
+  here_index::*,
+  space::*,
+};
+
+
+/// Length in km of one degree of latitude
+const KM_PER_DEGREE: f64 = 111.32;
+
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetHeresWithinInput {
+  pub session_eh: EntryHashB64,
+  pub center: GeoCoord,
+  pub radius_km: f64,
+}
+
+
+/// Heres of a geo session within a great-circle distance of a position
+#[hdk_extern]
+fn get_heres_within(input: GetHeresWithinInput) -> ExternResult<Vec<HereOutput>> {
+  let system = get_session_space_info(input.session_eh.clone())?.coordinate_system();
+  if system != CoordinateSystem::Geo {
+    return zome_error!("Session is not on a geo space");
+  }
+  let center = input.center;
+  let is_within = |here: &HereOutput| parse_position(&here.entry.value, system)
+    .map(|coord| great_circle_km(center, GeoCoord {lat: coord.y, lon: coord.x}) <= input.radius_km)
+    .unwrap_or(false);
+  /// Bounding box in degrees around the circle
+  let d_lat = input.radius_km / KM_PER_DEGREE;
+  let d_lon = input.radius_km / (KM_PER_DEGREE * center.lat.to_radians().cos());
+  let min = Coord {x: center.lon - d_lon, y: center.lat - d_lat};
+  let max = Coord {x: center.lon + d_lon, y: center.lat + d_lat};
+  /// The box wraps around a pole or the antimeridian: scan the session
+  let candidates = if !d_lon.is_finite() || min.y < -90.0 || max.y > 90.0 || min.x < -180.0 || max.x > 180.0 {
+    get_heres(input.session_eh)?
+  } else {
+    get_heres_in_box_inner(input.session_eh, system, None, min, max)?
+  };
+  Ok(candidates.into_iter().filter(is_within).collect())
+}
+
+
+/// Export the Heres of a geo session as a GeoJSON FeatureCollection.
+/// Meta values are parsed as JSON when possible and kept as strings otherwise.
+#[hdk_extern]
+fn export_session_geojson(session_eh: EntryHashB64) -> ExternResult<String> {
+  let system = get_session_space_info(session_eh.clone())?.coordinate_system();
+  if system != CoordinateSystem::Geo {
+    return zome_error!("Session is not on a geo space");
+  }
+  let mut features = Vec::new();
+  for here in get_heres(session_eh)? {
+    let Some(coord) = parse_position(&here.entry.value, system)
+      else {continue};
+    let mut properties = serde_json::Map::new();
+    for (key, value) in here.entry.meta.iter() {
+      let value = serde_json::from_str::<serde_json::Value>(value)
+        .unwrap_or_else(|_| serde_json::Value::String(value.clone()));
+      properties.insert(key.clone(), value);
+    }
+    properties.insert("author".to_string(), serde_json::json!(here.author.to_string()));
+    properties.insert("linkAh".to_string(), serde_json::json!(here.link_ah.to_string()));
+    features.push(serde_json::json!({
+      "type": "Feature",
+      "geometry": {"type": "Point", "coordinates": [coord.x, coord.y]},
+      "properties": properties,
+    }));
+  }
+  let collection = serde_json::json!({"type": "FeatureCollection", "features": features});
+  Ok(collection.to_string())
+}
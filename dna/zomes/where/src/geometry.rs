@@ -0,0 +1,53 @@
+use hdk::prelude::*;
+
+use where_integrity::*;
+
+
+/// Mean radius of the Earth in km
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+
+
+/// Position on a 2D surface, the format of `Here.value` on cartesian spaces
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub struct Coord {
+  pub x: f64,
+  pub y: f64,
+}
+
+
+/// Parse the JSON value of a Here, if it is a 2D position
+pub fn parse_coord(value: &str) -> Option<Coord> {
+  serde_json::from_str::<Coord>(value).ok()
+}
+
+
+/// Parse the JSON value of a Here as a 2D position in the given coordinate system.
+/// Geo positions map longitude to x and latitude to y.
+pub fn parse_position(value: &str, system: CoordinateSystem) -> Option<Coord> {
+  match system {
+    CoordinateSystem::Cartesian => parse_coord(value),
+    CoordinateSystem::Geo => {
+      let geo = serde_json::from_str::<GeoCoord>(value).ok()?;
+      Some(Coord {x: geo.lon, y: geo.lat})
+    },
+  }
+}
+
+
+/// Great-circle distance in km between two positions (haversine formula)
+pub fn great_circle_km(a: GeoCoord, b: GeoCoord) -> f64 {
+  let (lat_a, lat_b) = (a.lat.to_radians(), b.lat.to_radians());
+  let d_lat = lat_b - lat_a;
+  let d_lon = (b.lon - a.lon).to_radians();
+  let h = (d_lat / 2.0).sin().powi(2) + lat_a.cos() * lat_b.cos() * (d_lon / 2.0).sin().powi(2);
+  2.0 * EARTH_RADIUS_KM * h.sqrt().min(1.0).asin()
+}
+
+
+/// Euclidean distance, or great-circle distance in km on geo spaces
+pub fn distance(system: CoordinateSystem, a: Coord, b: Coord) -> f64 {
+  match system {
+    CoordinateSystem::Cartesian => ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt(),
+    CoordinateSystem::Geo => great_circle_km(GeoCoord {lat: a.y, lon: a.x}, GeoCoord {lat: b.y, lon: b.x}),
+  }
+}
@@ -6,6 +6,13 @@ use zome_utils::*;
 use where_integrity::*;
 use crate::{
     placement_session::*,
+    here_index::*,
+    migrate::*,
+    profiles::*,
+    reactions::*,
+    snapshot::*,
+    space::*,
+    voting::*,
 };
 
 
@@ -15,7 +22,12 @@ pub struct AddHereInput {
     pub space_eh: EntryHashB64,
     pub session_index: u32,
     pub value: String,
+    /// Identity fields (see `AUTHOR_META_KEYS`) are kept as sent, `with_profiles` in GetHeresInput reads the profiles zome instead
+    #[serde(default)]
     pub meta: BTreeMap<String, String>,
+    /// Time-to-live of the Here, in seconds
+    #[serde(default)]
+    pub maybe_ttl_secs: Option<u64>,
 }
 
 /// Input to update a Here
@@ -29,39 +41,174 @@ pub struct UpdateHereInput {
 #[hdk_extern]
 fn add_here(input: AddHereInput) -> ExternResult<ActionHashB64> {
     //debug!("add_here(): {:?}", input);
+    let (here, system) = new_here(input, None)?;
+    create_here(here, system)
+}
+
+
+/// Build a Here from the input, in the session at the given index of the space
+fn new_here(input: AddHereInput, maybe_origin_ah: Option<ActionHash>) -> ExternResult<(Here, CoordinateSystem)> {
     /// Find session
-    let get_input = GetSessionInput {space_eh: input.space_eh.into(), index: input.session_index};
+    let system = get_space_info(input.space_eh.clone())?.coordinate_system();
+    let get_input = GetSessionInput {space_eh: input.space_eh, index: input.session_index};
     let maybe_session_eh = get_session(get_input)?;
     let Some(session_eh) =  maybe_session_eh
         else {return zome_error!("Session not found")};
-    let session_eh64: EntryHashB64 = session_eh.into();
-    /// Create and link 'Here'
-    let here = Here {value: input.value, session_eh: session_eh64.clone(), meta: input.meta};
+    let maybe_expires_at = match input.maybe_ttl_secs {
+        Some(ttl) => {
+            let now = sys_time()?.as_micros();
+            let maybe_micros = i64::try_from(ttl).ok()
+                .and_then(|ttl| ttl.checked_mul(1_000_000))
+                .and_then(|ttl_micros| now.checked_add(ttl_micros));
+            let Some(micros) = maybe_micros
+                else { return zome_error!("Time-to-live is too large: {} seconds", ttl) };
+            Some(Timestamp::from_micros(micros))
+        },
+        None => None,
+    };
+    let here = Here {
+        value: input.value,
+        session_eh,
+        meta: input.meta,
+        maybe_expires_at,
+        maybe_origin_ah: maybe_origin_ah.map(|ah| ah.into()),
+    };
+    Ok((here, system))
+}
+
+
+/// Create a Here and link it to its session
+pub fn create_here(here: Here, system: CoordinateSystem) -> ExternResult<ActionHashB64> {
+    let session_eh: EntryHash = here.session_eh.clone().into();
+    /// Advisory, see `is_session_closed()`
+    if is_session_closed(session_eh.clone())? {
+        return zome_error!("Session is closed");
+    }
+    check_vote_budget(session_eh.clone())?;
     let here_eh = hash_entry(here.clone())?;
     create_entry(WhereEntry::Here(here.clone()))?;
-    let link_ah = create_link(session_eh64, here_eh, WhereLinkType::All, LinkTag::from(()))?;
+    let link_ah = create_link(session_eh.clone(), here_eh.clone(), WhereLinkType::All, here_link_tag(&here)?)?;
+    index_here(&session_eh, system, &here, here_eh, &link_ah)?;
+    /// Lets us find the Heres to delete when they expire, see `delete_expired_heres()`
+    if let Some(expires_at) = here.maybe_expires_at {
+        let me = agent_info()?.agent_latest_pubkey;
+        create_link(me, link_ah.clone(), WhereLinkType::Expiring, expiry_tag(expires_at))?;
+    }
     Ok(link_ah.into())
 }
 
+
+/// Replace a Here by a new version, keeping its identity so that its comments, reactions and attachments follow
 #[hdk_extern]
 fn update_here(input: UpdateHereInput) -> ExternResult<ActionHashB64> {
+    let here_id = get_here_id(input.old_here_ah.clone().into())?;
     delete_here(input.old_here_ah)?;
-    add_here(input.new_here)
+    let (here, system) = new_here(input.new_here, Some(here_id.clone()))?;
+    let link_ah = create_here(here, system)?;
+    create_link(here_id, ActionHash::from(link_ah.clone()), WhereLinkType::HereUpdates, LinkTag::from(()))?;
+    Ok(link_ah)
+}
+
+
+/// Identity of a Here, given the session link of any of its versions
+pub fn get_here_id(link_ah: ActionHash) -> ExternResult<ActionHash> {
+    let here = get_here_of_link(&link_ah)?;
+    Ok(here.here_id(&link_ah))
+}
+
+
+/// The Here entry of a session link, deleted or not
+pub fn get_here_of_link(link_ah: &ActionHash) -> ExternResult<Here> {
+    let Some(record) = get(link_ah.clone(), GetOptions::content())?
+        else { return zome_error!("Here not found") };
+    let Action::CreateLink(create) = record.action()
+        else { return zome_error!("Not the session link of a Here") };
+    let Some(here_eh) = create.target_address.clone().into_entry_hash()
+        else { return zome_error!("Not the session link of a Here") };
+    get_typed_from_eh::<Here>(here_eh)
+}
+
+
+/// Latest live version of a Here, given its identity
+pub fn get_current_here_output(here_id: ActionHash) -> ExternResult<Option<HereOutput>> {
+    let mut links = get_links(here_id.clone(), WhereLinkType::HereUpdates, None)?;
+    links.sort_by_key(|link| link.timestamp);
+    let versions = links.into_iter().rev()
+        .filter_map(|link| link.target.into_action_hash())
+        .chain(std::iter::once(here_id));
+    for link_ah in versions {
+        if let Some(here) = get_live_here_output(link_ah)? {
+            return Ok(Some(here));
+        }
+    }
+    Ok(None)
+}
+
+
+/// Output of a session link, or None if the link was deleted or its Here expired
+pub fn get_live_here_output(link_ah: ActionHash) -> ExternResult<Option<HereOutput>> {
+    let Some(Details::Record(details)) = get_details(link_ah.clone(), GetOptions::content())?
+        else { return Ok(None) };
+    if !details.deletes.is_empty() {
+        return Ok(None);
+    }
+    let Action::CreateLink(create) = details.record.action().clone()
+        else { return Ok(None) };
+    let Some(session_eh) = create.base_address.into_entry_hash()
+        else { return Ok(None) };
+    get_link_here_output(&session_eh, create.target_address, create.tag, create.author, link_ah, false)
 }
 
 #[hdk_extern]
-fn delete_here(link_ah: ActionHashB64) -> ExternResult<()> {
-    delete_link(link_ah.into())?;
+pub fn delete_here(link_ah: ActionHashB64) -> ExternResult<()> {
+    let link_ah: ActionHash = link_ah.into();
+    let maybe_create = match get(link_ah.clone(), GetOptions::content())? {
+        Some(record) => match record.action() {
+            Action::CreateLink(create) => Some(create.clone()),
+            _ => None,
+        },
+        None => None,
+    };
+    let maybe_session_eh = maybe_create.as_ref().and_then(|create| create.base_address.clone().into_entry_hash());
+    if let Some(session_eh) = maybe_session_eh.clone() {
+        if is_session_closed(session_eh)? {
+            return zome_error!("Session is closed");
+        }
+    }
+    delete_link(link_ah.clone())?;
+    /// Remove from spatial index, best-effort: the Here is deleted even if this fails
+    if let (Some(create), Some(session_eh)) = (maybe_create, maybe_session_eh) {
+        let Some(here_eh) = create.target_address.into_entry_hash()
+            else { return Ok(()) };
+        let result = get_typed_from_eh::<Here>(here_eh)
+            .and_then(|here| unindex_here(&session_eh, &here, &link_ah));
+        if let Err(e) = result {
+            debug!("delete_here(): Failed to unindex Here {}: {:?}", link_ah, e);
+        }
+    }
     Ok(())
 }
 
 /// Input to the create channel call
-#[derive(Debug, Serialize, Deserialize, SerializedBytes)]
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes)]
 #[serde(rename_all = "camelCase")]
 pub struct HereOutput {
     pub entry: Here,
     pub link_ah: ActionHashB64,
     pub author: AgentPubKeyB64,
+    /// Number of reactions per emoji, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maybe_reactions: Option<BTreeMap<String, u32>>,
+    /// Current profile of the author, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maybe_profile: Option<AuthorProfile>,
+}
+
+impl HereOutput {
+    /// Identity of the Here across updates, the key of its comments, reactions and attachments
+    pub fn here_id(&self) -> ActionHashB64 {
+        self.entry.here_id(&self.link_ah.clone().into()).into()
+    }
 }
 
 
@@ -73,38 +220,210 @@ pub struct HereOutput {
 
 
 #[hdk_extern]
-fn get_heres(session_eh: EntryHashB64) -> ExternResult<Vec<HereOutput>> {
+pub fn get_heres(session_eh: EntryHashB64) -> ExternResult<Vec<HereOutput>> {
     //debug!("get_heres() called: {:?}", session_eh);
     /// make sure its a session
     let _session = get_session_from_eh(session_eh.clone())?;
     /// Get links
-    let heres = get_heres_inner(session_eh.into())?;
+    let mut heres = get_heres_inner(session_eh.clone().into(), false)?;
+    apply_session_transform(session_eh, &mut heres)?;
     //debug!("get_heres() result: {:?}", heres);
     Ok(heres)
 }
 
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetHeresInput {
+    pub session_eh: EntryHashB64,
+    /// Fetch every Here entry instead of using the link summaries
+    #[serde(default)]
+    pub full_entries: bool,
+    /// Include the reaction counts of each Here
+    #[serde(default)]
+    pub with_reactions: bool,
+    /// Include the current profile of each Here's author
+    #[serde(default)]
+    pub with_profiles: bool,
+}
+
 ///
-fn get_heres_inner(base: EntryHash) -> ExternResult<Vec<HereOutput>> {
-    let links = get_links(base, WhereLinkType::All, None)?;
+#[hdk_extern]
+fn get_heres_with_options(input: GetHeresInput) -> ExternResult<Vec<HereOutput>> {
+    let _session = get_session_from_eh(input.session_eh.clone())?;
+    let mut heres = get_heres_inner(input.session_eh.clone().into(), input.full_entries)?;
+    apply_session_transform(input.session_eh, &mut heres)?;
+    if input.with_reactions {
+        for here in heres.iter_mut() {
+            here.maybe_reactions = Some(get_reaction_counts(here.here_id().into())?);
+        }
+    }
+    if input.with_profiles {
+        add_author_profiles(&mut heres)?;
+    }
+    Ok(heres)
+}
+
+
+///
+fn get_heres_inner(base: EntryHash, full_entries: bool) -> ExternResult<Vec<HereOutput>> {
+    let links = get_links(base.clone(), WhereLinkType::All, None)?;
     let mut output = Vec::with_capacity(links.len());
     /// Get details of every link on the target and create the message.
-    for link in links.into_iter().map(|link| link) {
+    for link in links.into_iter() {
         //debug!("get_heres_inner() link: {:?}", link);
-        let here_eh = link.target.clone().into_entry_hash().unwrap();
-        let details =  get_details(here_eh, GetOptions::content())?;
-        let Some(Details::Entry(EntryDetails {entry, mut actions, .. })) = details
-            else {continue};
-        /// Turn the entry into a HereOutput
-        let entry: Here = entry.try_into()?;
-        let Some(signed_action) = actions.pop()
+        let Some(w) = get_link_here_output(&base, link.target, link.tag, link.author, link.create_link_hash, full_entries)?
             else {continue};
-        /// Create the output for the UI
-        let w = HereOutput {
-            entry,
-            link_ah: link.create_link_hash.into(),
-            author: signed_action.action().author().clone().into()
-        };
         output.push(w);
     }
     Ok(output)
 }
+
+
+/// Build the output of a session link from its tag summary, which validation checked against the Here.
+/// Falls back to the Here entry for links without a summary (legacy links or large metas),
+/// or when `full_entries` is set.
+pub fn get_link_here_output(session_eh: &EntryHash, target: AnyLinkableHash, tag: LinkTag, author: AgentPubKey, link_ah: ActionHash, full_entries: bool) -> ExternResult<Option<HereOutput>> {
+    let here_eh = target.into_entry_hash().unwrap();
+    let Some(summary) = HereSummary::from_tag(tag).filter(|_| !full_entries)
+        else { return get_here_output(here_eh, link_ah) };
+    let now = sys_time()?;
+    if summary.maybe_expires_at.map(|expires_at| expires_at <= now).unwrap_or(false) {
+        return Ok(None);
+    }
+    let entry = Here {
+        value: summary.value,
+        session_eh: session_eh.clone().into(),
+        meta: summary.meta,
+        maybe_expires_at: summary.maybe_expires_at,
+        maybe_origin_ah: summary.maybe_origin_ah,
+    };
+    Ok(Some(HereOutput {entry, link_ah: link_ah.into(), author: author.into(), maybe_reactions: None, maybe_profile: None}))
+}
+
+
+/// Expiry of the Here of a session link, from the summary in its tag or else from the Here
+fn get_link_expiry(create_link: &CreateLink) -> ExternResult<Option<Timestamp>> {
+    if let Some(summary) = HereSummary::from_tag(create_link.tag.clone()) {
+        return Ok(summary.maybe_expires_at);
+    }
+    let Some(here_eh) = create_link.target_address.clone().into_entry_hash()
+        else { return Ok(None) };
+    Ok(get_typed_from_eh::<Here>(here_eh).ok().and_then(|here| here.maybe_expires_at))
+}
+
+
+/// Get the Here entry and its author, and create the output for the UI.
+/// Returns None if the Here has expired.
+pub fn get_here_output(here_eh: EntryHash, link_ah: ActionHash) -> ExternResult<Option<HereOutput>> {
+    let details =  get_details(here_eh, GetOptions::content())?;
+    let Some(Details::Entry(EntryDetails {entry, mut actions, .. })) = details
+        else {return Ok(None)};
+    /// Turn the entry into a HereOutput
+    let entry: Here = entry.try_into()?;
+    let now = sys_time()?;
+    if entry.maybe_expires_at.map(|expires_at| expires_at <= now).unwrap_or(false) {
+        return Ok(None);
+    }
+    let Some(signed_action) = actions.pop()
+        else {return Ok(None)};
+    /// Create the output for the UI
+    let w = HereOutput {
+        entry,
+        link_ah: link_ah.into(),
+        author: signed_action.action().author().clone().into(),
+        maybe_reactions: None,
+        maybe_profile: None,
+    };
+    Ok(Some(w))
+}
+
+
+/// The most recent Here of each agent in a session, in order of first appearance.
+/// Heres are as committed: the session's transform overlay is not applied.
+pub fn get_latest_heres_per_agent(session_eh: EntryHash) -> ExternResult<Vec<HereOutput>> {
+    let links = get_links(session_eh.clone(), WhereLinkType::All, None)?;
+    let mut latest: Vec<(Timestamp, HereOutput)> = Vec::new();
+    for link in links {
+        let Some(here) = get_link_here_output(&session_eh, link.target, link.tag, link.author, link.create_link_hash, false)?
+            else {continue};
+        match latest.iter_mut().find(|(_, other)| other.author == here.author) {
+            Some(pair) => if link.timestamp > pair.0 { *pair = (link.timestamp, here) },
+            None => latest.push((link.timestamp, here)),
+        }
+    }
+    Ok(latest.into_iter().map(|(_, here)| here).collect())
+}
+
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetHeresSinceInput {
+    pub session_eh: EntryHashB64,
+    pub since: Timestamp,
+}
+
+/// Changes to the Heres of a session after a point in time
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeresSinceOutput {
+    pub created: Vec<HereOutput>,
+    /// Session links deleted, i.e. `link_ah` of removed Heres
+    pub deleted: Vec<ActionHashB64>,
+    /// Value of `since` for the next call
+    pub cursor: Timestamp,
+}
+
+
+/// Heres created and deleted in a session after the given time, based on the link timestamps.
+/// Heres that expired after the given time count as deleted at their expiry.
+/// A Here created and deleted since then only appears as deleted.
+/// Links received late through gossip may carry an older timestamp than the cursor,
+/// so clients should still call `get_heres` from time to time.
+#[hdk_extern]
+fn get_heres_since(input: GetHeresSinceInput) -> ExternResult<HeresSinceOutput> {
+    let session_eh = EntryHash::from(input.session_eh);
+    let details = get_link_details(session_eh.clone(), WhereLinkType::All, None)?;
+    let now = sys_time()?;
+    let mut cursor = input.since;
+    let mut created = Vec::new();
+    let mut deleted = Vec::new();
+    for (create, deletes) in details.into_inner() {
+        let link_ah = create.as_hash().clone();
+        let Action::CreateLink(create_link) = create.action()
+            else {continue};
+        let mut is_deleted = false;
+        for delete in deletes {
+            let timestamp = delete.action().timestamp();
+            is_deleted = true;
+            if timestamp > input.since {
+                deleted.push(link_ah.clone().into());
+                cursor = cursor.max(timestamp);
+            }
+        }
+        if !is_deleted {
+            if let Some(expires_at) = get_link_expiry(create_link)?.filter(|expires_at| *expires_at <= now) {
+                is_deleted = true;
+                if expires_at > input.since {
+                    deleted.push(link_ah.clone().into());
+                    cursor = cursor.max(expires_at);
+                }
+            }
+        }
+        if create_link.timestamp <= input.since {
+            continue;
+        }
+        cursor = cursor.max(create_link.timestamp);
+        if is_deleted {
+            continue;
+        }
+        let Some(here) = get_link_here_output(&session_eh, create_link.target_address.clone(), create_link.tag.clone(), create_link.author.clone(), link_ah, false)?
+            else {continue};
+        created.push(here);
+    }
+    deleted.dedup();
+    apply_session_transform(session_eh.into(), &mut created)?;
+    Ok(HeresSinceOutput {created, deleted, cursor})
+}
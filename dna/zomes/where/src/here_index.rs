@@ -0,0 +1,323 @@
+use hdk::prelude::*;
+use hdk::hash_path::path::{Component, TypedPath};
+use holo_hash::EntryHashB64;
+use zome_utils::*;
+
+use where_integrity::*;
+use crate::{
+  geometry::*,
+  here::*,
+  migrate::*,
+  space::*,
+};
+
+
+/// Root path of the spatial index of Heres
+pub const HERE_INDEX_ROOT_PATH: &'static str = "here_index";
+/// Above this number of coarse cells, a box query scans the whole session instead
+pub const MAX_QUERIED_CELLS: i64 = 64;
+/// Number of rings of coarse cells searched around the center by nearest queries
+pub const MAX_NEAREST_RINGS: i64 = 4;
+
+
+/// Sizes, in value units, of the two levels of cells of the spatial index
+#[derive(Clone, Copy, Debug)]
+pub struct GridSpec {
+  pub coarse: f64,
+  pub fine: f64,
+}
+
+pub const CARTESIAN_GRID: GridSpec = GridSpec {coarse: 1024.0, fine: 64.0};
+/// In degrees, fine cells are about 1 km wide at the equator
+pub const GEO_GRID: GridSpec = GridSpec {coarse: 8.0, fine: 0.01};
+
+impl GridSpec {
+  pub fn of(system: CoordinateSystem) -> Self {
+    match system {
+      CoordinateSystem::Cartesian => CARTESIAN_GRID,
+      CoordinateSystem::Geo => GEO_GRID,
+    }
+  }
+}
+
+
+type Cell = (i64, i64);
+
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetHeresInBoxInput {
+  pub session_eh: EntryHashB64,
+  pub min: Coord,
+  pub max: Coord,
+}
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetNearestHeresInput {
+  pub session_eh: EntryHashB64,
+  pub center: Coord,
+  pub count: u32,
+}
+
+
+/// Link a new Here from the index cell of its position, tagged with its session link
+pub fn index_here(session_eh: &EntryHash, system: CoordinateSystem, here: &Here, here_eh: EntryHash, link_ah: &ActionHash) -> ExternResult<()> {
+  let Some(coord) = parse_position(&here.value, system)
+    else { return Ok(()) };
+  let grid = GridSpec::of(system);
+  let path = fine_cell_path(session_eh, cell_of(coord, grid.coarse), cell_of(coord, grid.fine))?;
+  path.ensure()?;
+  create_link(path.path_entry_hash()?, here_eh, WhereLinkType::HereIndex, LinkTag::new(link_ah.get_raw_39().to_vec()))?;
+  Ok(())
+}
+
+
+/// Delete the index link of a Here's session link.
+/// Doesn't need the session's space: a Here value parses as a position in one coordinate system only.
+pub fn unindex_here(session_eh: &EntryHash, here: &Here, link_ah: &ActionHash) -> ExternResult<()> {
+  for system in [CoordinateSystem::Cartesian, CoordinateSystem::Geo] {
+    let Some(coord) = parse_position(&here.value, system)
+      else {continue};
+    let grid = GridSpec::of(system);
+    let path = fine_cell_path(session_eh, cell_of(coord, grid.coarse), cell_of(coord, grid.fine))?;
+    let tag = LinkTag::new(link_ah.get_raw_39().to_vec());
+    for link in get_links(path.path_entry_hash()?, WhereLinkType::HereIndex, Some(tag))? {
+      delete_link(link.create_link_hash)?;
+    }
+  }
+  Ok(())
+}
+
+
+/// Heres of a session inside a bounding box, fetching only the index cells it overlaps.
+/// On geo spaces, x is the longitude and y the latitude.
+#[hdk_extern]
+fn get_heres_in_box(input: GetHeresInBoxInput) -> ExternResult<Vec<HereOutput>> {
+  let space = get_session_space_info(input.session_eh.clone())?;
+  get_heres_in_box_inner(input.session_eh, space.coordinate_system(), space.bounds(), input.min, input.max)
+}
+
+
+/// Heres of a session inside a bounding box, clamped to the bounds of the space if known
+pub fn get_heres_in_box_inner(session_eh64: EntryHashB64, system: CoordinateSystem, maybe_bounds: Option<(Coord, Coord)>, min: Coord, max: Coord) -> ExternResult<Vec<HereOutput>> {
+  if ![min.x, min.y, max.x, max.y].iter().all(|v| v.is_finite()) {
+    return zome_error!("Box corners must be finite numbers");
+  }
+  let (min, max) = match maybe_bounds {
+    Some((low, high)) => (
+      Coord {x: min.x.max(low.x), y: min.y.max(low.y)},
+      Coord {x: max.x.min(high.x), y: max.y.min(high.y)},
+    ),
+    None => (min, max),
+  };
+  if min.x > max.x || min.y > max.y {
+    return Ok(vec![]);
+  }
+  let session_eh: EntryHash = session_eh64.clone().into();
+  let grid = GridSpec::of(system);
+  let (cmin, cmax) = (cell_of(min, grid.coarse), cell_of(max, grid.coarse));
+  /// In f64 as the cell coordinates of an unbounded box can be anywhere in i64
+  let cell_count = (cmax.0 as f64 - cmin.0 as f64 + 1.0) * (cmax.1 as f64 - cmin.1 as f64 + 1.0);
+  let is_inside = |coord: Coord| coord.x >= min.x && coord.x <= max.x && coord.y >= min.y && coord.y <= max.y;
+  /// Too many cells to visit: cheaper to scan the session.
+  /// Same for migrated sessions, as the index holds the original positions.
+  if cell_count > MAX_QUERIED_CELLS as f64 || get_session_transform(session_eh64.clone())?.is_some() {
+    let heres = get_heres(session_eh64)?.into_iter()
+      .filter(|here| parse_position(&here.entry.value, system).map(is_inside).unwrap_or(false))
+      .collect();
+    return Ok(heres);
+  }
+  let mut found: Vec<HereOutput> = Vec::new();
+  for cx in cmin.0..=cmax.0 {
+    for cy in cmin.1..=cmax.1 {
+      for (here_eh, link_ah) in get_coarse_cell_links(&session_eh, (cx, cy), grid, Some((min, max)))? {
+        let Some(here) = get_here_output(here_eh, link_ah)?
+          else {continue};
+        if parse_position(&here.entry.value, system).map(is_inside).unwrap_or(false)
+          && !found.iter().any(|other| other.link_ah == here.link_ah) {
+          found.push(here);
+        }
+      }
+    }
+  }
+  Ok(found)
+}
+
+
+/// The `count` Heres of a session closest to a point, searching rings of coarse cells around it.
+/// On geo spaces, x is the longitude, y the latitude and distances are great-circle distances.
+/// Rings stop once the `count`-th closest Here found is within the radius they fully cover,
+/// otherwise, or on migrated sessions, the whole session is scanned.
+/// Rings walk coarse cells so that few anchors are fetched per ring, while the fine cells under them
+/// keep each anchor's link count small and let box queries skip the parts outside the box.
+#[hdk_extern]
+fn get_nearest_heres(input: GetNearestHeresInput) -> ExternResult<Vec<HereOutput>> {
+  let session_eh: EntryHash = input.session_eh.clone().into();
+  let center = input.center;
+  let count = input.count as usize;
+  if count == 0 {
+    return Ok(vec![]);
+  }
+  if !center.x.is_finite() || !center.y.is_finite() {
+    return zome_error!("Center must be finite numbers");
+  }
+  let system = get_session_space_info(input.session_eh.clone())?.coordinate_system();
+  let grid = GridSpec::of(system);
+  let origin = cell_of(center, grid.coarse);
+  let mut candidates: Vec<(f64, HereOutput)> = Vec::new();
+  let mut is_complete = false;
+  /// The index holds the original positions of migrated sessions: skip to the scan
+  let max_rings = if get_session_transform(input.session_eh.clone())?.is_some() { -1 } else { MAX_NEAREST_RINGS };
+  for ring in 0..=max_rings {
+    for cell in ring_cells(origin, ring) {
+      for (here_eh, link_ah) in get_coarse_cell_links(&session_eh, cell, grid, None)? {
+        let Some(here) = get_here_output(here_eh, link_ah)?
+          else {continue};
+        let Some(coord) = parse_position(&here.entry.value, system)
+          else {continue};
+        candidates.push((distance(system, center, coord), here));
+      }
+    }
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+    if candidates.len() >= count && candidates[count - 1].0 <= covered_radius(system, center, origin, ring, grid.coarse) {
+      is_complete = true;
+      break;
+    }
+  }
+  /// A Here outside the rings could be closer than the ones found: scan the session
+  if !is_complete {
+    candidates = get_heres(input.session_eh)?.into_iter()
+      .filter_map(|here| parse_position(&here.entry.value, system).map(|coord| (distance(system, center, coord), here)))
+      .collect();
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+  }
+  Ok(candidates.into_iter().take(count).map(|(_, here)| here).collect())
+}
+
+
+/// Distance from `center` within which every position is in the cells up to `ring` rings around `origin`,
+/// i.e. the distance to the nearest side of their square.
+/// On geo spaces, the square stops at the poles and the antimeridian, where positions wrap around.
+fn covered_radius(system: CoordinateSystem, center: Coord, origin: Cell, ring: i64, size: f64) -> f64 {
+  let low = Coord {x: (origin.0 - ring) as f64 * size, y: (origin.1 - ring) as f64 * size};
+  let high = Coord {x: (origin.0 + ring + 1) as f64 * size, y: (origin.1 + ring + 1) as f64 * size};
+  match system {
+    CoordinateSystem::Cartesian => (center.x - low.x).min(high.x - center.x).min(center.y - low.y).min(high.y - center.y),
+    CoordinateSystem::Geo => {
+      /// To the nearest parallel, along a meridian
+      let d_lat = (center.y - low.y.max(-90.0)).min(high.y.min(90.0) - center.y).max(0.0);
+      /// To the nearest meridian, along the great circle crossing it at a right angle
+      let d_lon = (center.x - low.x.max(-180.0)).min(high.x.min(180.0) - center.x).clamp(0.0, 90.0);
+      let to_meridian = (center.y.to_radians().cos() * d_lon.to_radians().sin()).clamp(0.0, 1.0).asin();
+      EARTH_RADIUS_KM * d_lat.to_radians().min(to_meridian)
+    },
+  }
+}
+
+
+///
+fn cell_of(coord: Coord, size: f64) -> Cell {
+  ((coord.x / size).floor() as i64, (coord.y / size).floor() as i64)
+}
+
+
+/// Cells at exactly `ring` cells (Chebyshev distance) from `origin`
+fn ring_cells(origin: Cell, ring: i64) -> Vec<Cell> {
+  let mut cells = Vec::new();
+  for dx in -ring..=ring {
+    for dy in -ring..=ring {
+      if dx.abs() == ring || dy.abs() == ring {
+        cells.push((origin.0 + dx, origin.1 + dy));
+      }
+    }
+  }
+  cells
+}
+
+
+///
+fn cell_component(cell: Cell) -> Component {
+  Component::from(format!("{}:{}", cell.0, cell.1))
+}
+
+///
+fn parse_cell_component(component: &Component) -> Option<Cell> {
+  let s = String::try_from(component).ok()?;
+  let (x, y) = s.split_once(':')?;
+  Some((x.parse().ok()?, y.parse().ok()?))
+}
+
+
+///
+fn coarse_cell_path(session_eh: &EntryHash, coarse: Cell) -> ExternResult<TypedPath> {
+  let components = vec![
+    Component::from(HERE_INDEX_ROOT_PATH),
+    Component::from(session_eh.get_raw_39().to_vec()),
+    cell_component(coarse),
+  ];
+  Path::from(components).typed(WhereLinkType::HereIndex)
+}
+
+///
+fn fine_cell_path(session_eh: &EntryHash, coarse: Cell, fine: Cell) -> ExternResult<TypedPath> {
+  let mut path = coarse_cell_path(session_eh, coarse)?;
+  path.path.append_component(cell_component(fine));
+  Ok(path)
+}
+
+
+/// Index links of the fine cells of a coarse cell, optionally only those overlapping a box.
+/// Returns the Here's EntryHash and its session link's ActionHash.
+fn get_coarse_cell_links(session_eh: &EntryHash, coarse: Cell, grid: GridSpec, maybe_box: Option<(Coord, Coord)>) -> ExternResult<Vec<(EntryHash, ActionHash)>> {
+  let mut result = Vec::new();
+  for child in coarse_cell_path(session_eh, coarse)?.children_paths()? {
+    let Some(fine) = child.path.leaf().and_then(parse_cell_component)
+      else {continue};
+    if let Some((min, max)) = maybe_box {
+      let (fx, fy) = (fine.0 as f64 * grid.fine, fine.1 as f64 * grid.fine);
+      if fx > max.x || fx + grid.fine < min.x || fy > max.y || fy + grid.fine < min.y {
+        continue;
+      }
+    }
+    for link in get_links(child.path_entry_hash()?, WhereLinkType::HereIndex, None)? {
+      let Some(here_eh) = link.target.into_entry_hash()
+        else {continue};
+      let Ok(link_ah) = ActionHash::from_raw_39(link.tag.into_inner())
+        else {continue};
+      result.push((here_eh, link_ah));
+    }
+  }
+  Ok(result)
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn cartesian_rings_cover_up_to_the_nearest_side() {
+    let center = Coord {x: 1020.0, y: 10.0};
+    let origin = cell_of(center, CARTESIAN_GRID.coarse);
+    assert_eq!(covered_radius(CoordinateSystem::Cartesian, center, origin, 0, CARTESIAN_GRID.coarse), 4.0);
+    assert_eq!(covered_radius(CoordinateSystem::Cartesian, center, origin, 1, CARTESIAN_GRID.coarse), 1028.0);
+  }
+
+  #[test]
+  fn geo_rings_stop_at_the_antimeridian_and_the_poles() {
+    let size = GEO_GRID.coarse;
+    // Half a degree from the antimeridian at 4 degrees north
+    let center = Coord {x: 179.5, y: 4.0};
+    let radius = covered_radius(CoordinateSystem::Geo, center, cell_of(center, size), 2, size);
+    let across = great_circle_km(GeoCoord {lat: 4.0, lon: 179.5}, GeoCoord {lat: 4.0, lon: -180.0});
+    assert!(radius <= across && radius > 0.99 * across);
+    // Near the north pole, positions on the other side are closer than the square's sides
+    let center = Coord {x: 0.0, y: 89.0};
+    let radius = covered_radius(CoordinateSystem::Geo, center, cell_of(center, size), 2, size);
+    let over_the_pole = great_circle_km(GeoCoord {lat: 89.0, lon: 0.0}, GeoCoord {lat: 89.0, lon: 180.0});
+    assert!(radius < over_the_pole);
+  }
+}
@@ -7,7 +7,7 @@ use where_integrity::*;
 #[hdk_extern]
 fn hide_space(space_eh64: EntryHashB64) -> ExternResult<ActionHash> {
   let my_agent_eh = EntryHash::from(agent_info().unwrap().agent_latest_pubkey);
-  return create_link(my_agent_eh, space_eh64, WhereLinkType::Hide, LinkTag::from(()));
+  create_link(my_agent_eh, space_eh64, WhereLinkType::Hide, LinkTag::from(()))
 }
 
 ///
@@ -3,6 +3,10 @@
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 #![allow(unused_attributes)]
+#![allow(clippy::redundant_static_lifetimes)]
+#![allow(clippy::empty_docs)]
+#![allow(clippy::empty_line_after_doc_comments)]
+#![allow(clippy::empty_line_after_outer_attr)]
 
 use hdk::prelude::*;
 
@@ -11,9 +15,28 @@ pub mod signals;
 pub mod here;
 pub mod placement_session;
 pub mod hide;
+pub mod space;
+pub mod geometry;
+pub mod zones;
+pub mod here_index;
+pub mod geo;
+pub mod stats;
+pub mod diff;
+pub mod carried;
+pub mod schedule;
+pub mod expiry;
+pub mod snapshot;
+pub mod comments;
+pub mod reactions;
+pub mod voting;
+pub mod migrate;
+pub mod profiles;
+pub mod markers;
+pub mod attachables;
 //pub mod play;
 
-pub const PLAYSET_DEFAULT_COORDINATOR_ZOME_NAME: &'static str = "where_playset";
+pub const PLAYSET_DEFAULT_COORDINATOR_ZOME_NAME: &'static str = "zPlayset";
+pub const PROFILES_DEFAULT_COORDINATOR_ZOME_NAME: &'static str = "profiles";
 
 
 #[hdk_extern]
@@ -27,6 +50,10 @@ fn init(_: ()) -> ExternResult<InitCallbackResult> {
         access: ().into(),
         functions: GrantedFunctions::Listed(functions),
     })?;
+    /// Create the sessions of recurring schedules while this agent is online
+    schedule("create_scheduled_sessions")?;
+    /// Delete our expired Heres while this agent is online
+    schedule("delete_expired_heres")?;
     Ok(InitCallbackResult::Pass)
 }
 
@@ -44,3 +71,18 @@ pub fn call_playset_zome<T>(fn_name: &str, payload: T) -> ExternResult<ZomeCallR
         payload,
     )
 }
+
+
+/// Helper function for calling the profiles-zome via inter-zome call
+pub fn call_profiles_zome<T>(fn_name: &str, payload: T) -> ExternResult<ZomeCallResponse>
+    where
+        T: serde::Serialize + std::fmt::Debug,
+{
+    call(
+        CallTargetCell::Local,
+        PROFILES_DEFAULT_COORDINATOR_ZOME_NAME,
+        fn_name.to_string().into(),
+        None,
+        payload,
+    )
+}
@@ -0,0 +1,176 @@
+use hdk::prelude::*;
+use holo_hash::{EntryHashB64, AgentPubKeyB64};
+use zome_utils::*;
+
+use crate::{
+  call_playset_zome,
+  here::*,
+  placement_session::*,
+  profiles::*,
+};
+
+
+/// Min hue difference, in degrees, between two agents of a space
+pub const MIN_HUE_GAP: f64 = 24.0;
+/// Hue step tried on collision, the golden angle spreads successive tries evenly
+const GOLDEN_ANGLE: f64 = 137.507_764;
+
+/// warning: duplicate of playset::RenderSvgMarkerInput
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderSvgMarkerInput {
+  pub svg_marker_eh: EntryHashB64,
+  pub color: String,
+  pub initials: String,
+}
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAgentColorInput {
+  pub agent: AgentPubKeyB64,
+  /// Avoid colors close to the ones of the other agents placed on this space
+  pub maybe_space_eh: Option<EntryHashB64>,
+}
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderAgentMarkerInput {
+  pub svg_marker_eh: EntryHashB64,
+  pub agent: AgentPubKeyB64,
+  pub maybe_space_eh: Option<EntryHashB64>,
+}
+
+
+/// Color of an agent as "#rrggbb".
+/// Without a space it is derived from the key only, so it is the same everywhere and never changes.
+/// Within a space it is the same on every device that sees the same Heres.
+#[hdk_extern]
+fn get_agent_color(input: GetAgentColorInput) -> ExternResult<String> {
+  let agent: AgentPubKey = input.agent.into();
+  let hue = match input.maybe_space_eh {
+    Some(space_eh) => get_space_hues(space_eh, &agent)?.into_iter()
+      .find(|(other, _)| other == &agent)
+      .map(|(_, hue)| hue)
+      .unwrap_or_else(|| agent_hue(&agent)),
+    None => agent_hue(&agent),
+  };
+  Ok(hue_to_hex(hue))
+}
+
+
+/// SvgMarker of an agent with its color and initials substituted by the playset zome
+#[hdk_extern]
+fn render_agent_marker(input: RenderAgentMarkerInput) -> ExternResult<String> {
+  let color = get_agent_color(GetAgentColorInput {agent: input.agent.clone(), maybe_space_eh: input.maybe_space_eh})?;
+  let initials = get_author_profile(input.agent)?
+    .map(|profile| initials_of(&profile.nickname))
+    .unwrap_or_else(|| "?".to_string());
+  let res = call_playset_zome("render_svg_marker", RenderSvgMarkerInput {svg_marker_eh: input.svg_marker_eh, color, initials})?;
+  let svg: String = decode_response(res)?;
+  Ok(svg)
+}
+
+
+/// Hue of every agent with a Here on the space, plus the given agent
+fn get_space_hues(space_eh: EntryHashB64, agent: &AgentPubKey) -> ExternResult<Vec<(AgentPubKey, f64)>> {
+  let mut agents: Vec<AgentPubKey> = vec![agent.clone()];
+  for session_eh in get_space_sessions(space_eh)? {
+    for here in get_heres(session_eh)? {
+      let author: AgentPubKey = here.author.into();
+      if !agents.contains(&author) {
+        agents.push(author);
+      }
+    }
+  }
+  Ok(assign_hues(agents))
+}
+
+
+/// Hue of each agent, moved away from the hues already taken.
+/// Agents are processed in key order so that every device assigns the same hues.
+fn assign_hues(mut agents: Vec<AgentPubKey>) -> Vec<(AgentPubKey, f64)> {
+  agents.sort_by(|a, b| a.get_raw_39().cmp(b.get_raw_39()));
+  agents.dedup();
+  let mut hues: Vec<(AgentPubKey, f64)> = Vec::with_capacity(agents.len());
+  for agent in agents {
+    let mut hue = agent_hue(&agent);
+    /// Give up when the space is too crowded
+    for _ in 0..(360.0 / MIN_HUE_GAP) as usize {
+      if hues.iter().all(|(_, other)| hue_gap(hue, *other) >= MIN_HUE_GAP) {
+        break;
+      }
+      hue = (hue + GOLDEN_ANGLE) % 360.0;
+    }
+    hues.push((agent, hue));
+  }
+  hues
+}
+
+
+/// Hue derived from the agent's key
+fn agent_hue(agent: &AgentPubKey) -> f64 {
+  let core = agent.get_raw_32();
+  let n = u32::from_be_bytes([core[0], core[1], core[2], core[3]]);
+  (n % 360) as f64
+}
+
+///
+fn hue_gap(a: f64, b: f64) -> f64 {
+  let d = (a - b).abs() % 360.0;
+  d.min(360.0 - d)
+}
+
+/// "#rrggbb" of a hue at 65% saturation and 50% lightness
+fn hue_to_hex(hue: f64) -> String {
+  let (s, l) = (0.65, 0.5);
+  let c = (1.0 - (2.0 * l - 1.0_f64).abs()) * s;
+  let x = c * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+  let m = l - c / 2.0;
+  let (r, g, b) = match (hue / 60.0) as u32 {
+    0 => (c, x, 0.0),
+    1 => (x, c, 0.0),
+    2 => (0.0, c, x),
+    3 => (0.0, x, c),
+    4 => (x, 0.0, c),
+    _ => (c, 0.0, x),
+  };
+  let to_byte = |v: f64| ((v + m) * 255.0).round() as u8;
+  format!("#{:02x}{:02x}{:02x}", to_byte(r), to_byte(g), to_byte(b))
+}
+
+/// First letter of the first two words, uppercased
+fn initials_of(name: &str) -> String {
+  name.split_whitespace()
+    .filter_map(|word| word.chars().next())
+    .take(2)
+    .flat_map(|c| c.to_uppercase())
+    .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Key whose hue is `n % 360`, the last byte tells keys apart
+  fn agent(n: u32, last: u8) -> AgentPubKey {
+    let mut core = [0u8; 36];
+    core[..4].copy_from_slice(&n.to_be_bytes());
+    core[31] = last;
+    AgentPubKey::from_raw_36(core.to_vec())
+  }
+
+  #[test]
+  fn colliding_keys_get_distinct_hues() {
+    let (a, b) = (agent(100, 1), agent(460, 2));
+    assert_eq!(agent_hue(&a), agent_hue(&b));
+    let hues = assign_hues(vec![b.clone(), a.clone()]);
+    assert_eq!(hues[0], (a, 100.0));
+    assert_eq!(hues[1].0, b);
+    assert!(hue_gap(hues[0].1, hues[1].1) >= MIN_HUE_GAP);
+    /// Same result whatever the order agents are found in
+    assert_eq!(hues, assign_hues(vec![hues[0].0.clone(), hues[1].0.clone()]));
+  }
+}
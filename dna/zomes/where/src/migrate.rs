@@ -0,0 +1,90 @@
+use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use zome_utils::*;
+
+use where_integrity::*;
+use crate::{
+  here::*,
+  placement_session::*,
+  space::*,
+};
+
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrateHeresInput {
+  pub session_eh: EntryHashB64,
+  pub transform: HereTransform,
+}
+
+
+/// Move every Here of a cartesian session by adding a transform overlay, e.g. after a resize of the space's surface.
+/// Only the session's creator can migrate it. The Here entries are not modified, so history is kept.
+#[hdk_extern]
+fn migrate_heres(input: MigrateHeresInput) -> ExternResult<EntryHashB64> {
+  if !input.transform.is_valid() {
+    return zome_error!("Transform must be finite with non-zero scales");
+  }
+  let session_eh: EntryHash = input.session_eh.clone().into();
+  if get_session_space_info(input.session_eh.clone())?.coordinate_system() != CoordinateSystem::Cartesian {
+    return zome_error!("Only Heres of cartesian spaces can be migrated");
+  }
+  let Some(session_ah) = get_my_session_ah(session_eh.clone())?
+    else { return zome_error!("Only the session's creator can migrate its Heres") };
+  let session_transform = SessionTransform {session_eh: input.session_eh, session_ah: session_ah.into(), transform: input.transform};
+  let transform_eh = hash_entry(session_transform.clone())?;
+  create_entry(WhereEntry::SessionTransform(session_transform))?;
+  create_link(session_eh, transform_eh.clone(), WhereLinkType::Transforms, LinkTag::from(()))?;
+  Ok(transform_eh.into())
+}
+
+
+/// All the transforms of a session composed in the order they were made, or None if it was never migrated
+#[hdk_extern]
+pub fn get_session_transform(session_eh: EntryHashB64) -> ExternResult<Option<HereTransform>> {
+  let mut links = get_links(session_eh, WhereLinkType::Transforms, None)?;
+  if links.is_empty() {
+    return Ok(None);
+  }
+  links.sort_by_key(|link| link.timestamp);
+  let mut composed = HereTransform::identity();
+  for link in links {
+    let session_transform = get_typed_from_eh::<SessionTransform>(link.target.into_entry_hash().unwrap())?;
+    composed = composed.then(&session_transform.transform);
+  }
+  Ok(Some(composed))
+}
+
+
+/// Apply the transform overlay of a session to Heres of that session
+pub fn apply_session_transform(session_eh: EntryHashB64, heres: &mut [HereOutput]) -> ExternResult<()> {
+  let Some(transform) = get_session_transform(session_eh)?
+    else { return Ok(()) };
+  for here in heres.iter_mut() {
+    here.entry.value = transform_value(&here.entry.value, &transform);
+  }
+  Ok(())
+}
+
+
+/// Apply the transform overlay of its session to a Here
+pub fn apply_here_session_transform(here: &mut Here) -> ExternResult<()> {
+  let Some(transform) = get_session_transform(here.session_eh.clone())?
+    else { return Ok(()) };
+  here.value = transform_value(&here.value, &transform);
+  Ok(())
+}
+
+
+/// Transform the "x" and "y" of a JSON value, keeping its other fields
+fn transform_value(value: &str, transform: &HereTransform) -> String {
+  let Ok(serde_json::Value::Object(mut object)) = serde_json::from_str::<serde_json::Value>(value)
+    else { return value.to_string() };
+  let (Some(x), Some(y)) = (object.get("x").and_then(|v| v.as_f64()), object.get("y").and_then(|v| v.as_f64()))
+    else { return value.to_string() };
+  let (x, y) = transform.apply(x, y);
+  object.insert("x".to_string(), serde_json::json!(x));
+  object.insert("y".to_string(), serde_json::json!(y));
+  serde_json::Value::Object(object).to_string()
+}
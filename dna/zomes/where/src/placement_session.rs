@@ -3,6 +3,7 @@ use holo_hash::EntryHashB64;
 use zome_utils::*;
 
 use where_integrity::*;
+use crate::carried::*;
 //use playset_integrity::*;
 
 
@@ -17,11 +18,11 @@ pub struct GetSessionInput {
 #[hdk_extern]
 pub fn get_session(input: GetSessionInput) -> ExternResult<Option<EntryHashB64>> {
   /// Make sure its a space
-  let _ = is_valid_space(input.space_eh.clone().into())?;
+  is_valid_space(input.space_eh.clone().into())?;
   /// Get session at index
   let tag: LinkTag = format!("{}", input.index).as_bytes().to_vec().into();
   let links = get_links(input.space_eh.clone(), WhereLinkType::All, Some(tag.clone()))?;
-  if links.len() == 0 {
+  if links.is_empty() {
     debug!("get_session(): Session {} not found for space '{:?}'", input.index, input.space_eh);
     return Ok(None);
   }
@@ -47,6 +48,18 @@ pub fn get_session_from_eh(session_eh: EntryHashB64) -> ExternResult<Option<Plac
 }
 
 
+/// Our creation action of a session entry, or None if we did not create it
+pub fn get_my_session_ah(session_eh: EntryHash) -> ExternResult<Option<ActionHash>> {
+  let Some(Details::Entry(details)) = get_details(session_eh, GetOptions::content())?
+    else { return zome_error!("Session not found") };
+  let me = agent_info()?.agent_latest_pubkey;
+  let maybe_ah = details.actions.into_iter()
+    .find(|action| action.action().author() == &me)
+    .map(|action| action.as_hash().clone());
+  Ok(maybe_ah)
+}
+
+
 ///
 pub fn is_valid_space(space_eh: EntryHash) -> ExternResult<()> {
   let _entry_type = get_entry_type_from_eh(space_eh)?;
@@ -62,10 +75,16 @@ pub fn is_valid_space(space_eh: EntryHash) -> ExternResult<()> {
 #[hdk_extern]
 pub fn get_space_sessions(space_eh: EntryHashB64) -> ExternResult<Vec<EntryHashB64>> {
   /// Make sure its a space
-  let _ = is_valid_space(space_eh.clone().into())?;
+  is_valid_space(space_eh.clone().into())?;
   // get links
   let links = get_links(space_eh, WhereLinkType::All, None)?;
-  let sessions = links.iter().map(|link| link.target.clone().into_entry_hash().unwrap().into()).collect();
+  let mut sessions: Vec<EntryHashB64> = Vec::new();
+  /// Scheduled sessions can be created by several agents
+  for session_eh in links.into_iter().map(|link| link.target.into_entry_hash().unwrap().into()) {
+    if !sessions.contains(&session_eh) {
+      sessions.push(session_eh);
+    }
+  }
   Ok(sessions)
 }
 
@@ -81,14 +100,12 @@ pub struct SpaceSessionsInput {
 #[hdk_extern]
 fn create_sessions(input: SpaceSessionsInput) -> ExternResult<Vec<EntryHashB64>> {
   /// Make sure its a space
-  let _ = is_valid_space(input.space_eh.clone().into())?;
+  is_valid_space(input.space_eh.clone().into())?;
   /// Create each session
-  let mut index = 0;
   let mut ehs = Vec::new();
-  for name in input.session_names {
-    let eh = create_session(input.space_eh.clone().into(), name, index)?;
+  for (index, name) in input.session_names.into_iter().enumerate() {
+    let eh = create_session(input.space_eh.clone().into(), name, index as u32)?;
     ehs.push(eh);
-    index += 1;
   }
   Ok(ehs)
 }
@@ -96,7 +113,15 @@ fn create_sessions(input: SpaceSessionsInput) -> ExternResult<Vec<EntryHashB64>>
 
 /// Argument is a Space so we are sure the hh is valid
 pub fn create_session(space_eh: EntryHash, name: String, index: u32) -> ExternResult<EntryHashB64> {
-  let session = PlacementSession { name, index, space_eh: space_eh.clone().into() };
+  let session = PlacementSession { name, index, space_eh: space_eh.clone().into(), maybe_opens_at: None, maybe_closes_at: None, maybe_vote_budget: None };
+  create_session_entry(session)
+}
+
+
+/// Create a session and link it to its space
+pub fn create_session_entry(session: PlacementSession) -> ExternResult<EntryHashB64> {
+  let space_eh: EntryHash = session.space_eh.clone().into();
+  let index = session.index;
   let session_eh = hash_entry(session.clone())?;
   let _hh = create_entry(WhereEntry::PlacementSession(session.clone()))?;
   let tag = format!("{}", index).as_bytes().to_vec();
@@ -127,12 +152,36 @@ pub fn get_next_session_index(space_eh: EntryHash) -> ExternResult<u32> {
 pub struct CreateNextSessionInput {
   name: String,
   space_eh: EntryHashB64,
+  /// Offer each agent's latest Here of the previous session as a placement to confirm or discard
+  #[serde(default)]
+  carry_forward: bool,
+  #[serde(default)]
+  maybe_opens_at: Option<Timestamp>,
+  #[serde(default)]
+  maybe_closes_at: Option<Timestamp>,
+  /// Voting mode: max number of Heres per agent
+  #[serde(default)]
+  maybe_vote_budget: Option<u32>,
 }
 
 #[hdk_extern]
 fn create_next_session(input: CreateNextSessionInput) -> ExternResult<(EntryHashB64, u32)> {
-  let space_eh: EntryHash = input.space_eh.into();
+  let space_eh: EntryHash = input.space_eh.clone().into();
   let next_index = get_next_session_index(space_eh.clone())?;
-  let eh = create_session(space_eh, input.name, next_index)?;
+  let session = PlacementSession {
+    name: input.name,
+    index: next_index,
+    space_eh: input.space_eh.clone(),
+    maybe_opens_at: input.maybe_opens_at,
+    maybe_closes_at: input.maybe_closes_at,
+    maybe_vote_budget: input.maybe_vote_budget,
+  };
+  let eh = create_session_entry(session)?;
+  if input.carry_forward && next_index > 0 {
+    let get_input = GetSessionInput {space_eh: input.space_eh, index: next_index - 1};
+    if let Some(previous_eh) = get_session(get_input)? {
+      carry_forward_heres(previous_eh.into(), eh.clone().into())?;
+    }
+  }
   Ok((eh, next_index))
 }
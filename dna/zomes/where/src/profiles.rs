@@ -0,0 +1,87 @@
+use hdk::prelude::*;
+use holo_hash::AgentPubKeyB64;
+use std::collections::BTreeMap;
+use zome_utils::*;
+
+use crate::{
+  call_profiles_zome,
+  here::*,
+  stats::*,
+};
+
+
+/// Meta keys of the author's identity that legacy Heres carry, only read when the author has no profile
+pub const AUTHOR_META_KEYS: [&'static str; 3] = ["authorName", "img", "color"];
+
+
+/// warning: partial duplicate of hc_zome_profiles_integrity::Profile
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileInfo {
+  pub nickname: String,
+  #[serde(default)]
+  pub fields: BTreeMap<String, String>,
+}
+
+/// Identity of a Here's author for display
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorProfile {
+  pub nickname: String,
+  pub maybe_avatar: Option<String>,
+  pub maybe_color: Option<String>,
+  /// Taken from the Here's meta because the author has no profile (legacy entries)
+  pub from_meta: bool,
+}
+
+
+/// Current profile of an agent from the profiles zome
+#[hdk_extern]
+pub fn get_author_profile(agent: AgentPubKeyB64) -> ExternResult<Option<AuthorProfile>> {
+  let Some(profile) = get_profile_info(agent.into())?
+    else { return Ok(None) };
+  Ok(Some(AuthorProfile {
+    nickname: profile.nickname,
+    maybe_avatar: profile.fields.get("avatar").cloned(),
+    maybe_color: profile.fields.get("color").cloned(),
+    from_meta: false,
+  }))
+}
+
+
+/// Set the author profile of each Here, falling back to the identity fields of its meta
+pub fn add_author_profiles(heres: &mut [HereOutput]) -> ExternResult<()> {
+  let mut cache: Vec<(AgentPubKeyB64, Option<AuthorProfile>)> = Vec::new();
+  for here in heres.iter_mut() {
+    let maybe_profile = match cache.iter().find(|(agent, _)| agent == &here.author) {
+      Some((_, maybe_profile)) => maybe_profile.clone(),
+      None => {
+        let maybe_profile = get_author_profile(here.author.clone())?;
+        cache.push((here.author.clone(), maybe_profile.clone()));
+        maybe_profile
+      },
+    };
+    here.maybe_profile = maybe_profile.or_else(|| {
+      let nickname = get_meta_string(&here.entry, "authorName")?;
+      Some(AuthorProfile {
+        nickname,
+        maybe_avatar: get_meta_string(&here.entry, "img"),
+        maybe_color: get_meta_string(&here.entry, "color"),
+        from_meta: true,
+      })
+    });
+  }
+  Ok(())
+}
+
+
+///
+fn get_profile_info(agent: AgentPubKey) -> ExternResult<Option<ProfileInfo>> {
+  let res = call_profiles_zome("get_agent_profile", agent)?;
+  let maybe_record: Option<Record> = decode_response(res)?;
+  let Some(record) = maybe_record
+    else { return Ok(None) };
+  let maybe_profile = record.entry().to_app_option::<ProfileInfo>()
+    .map_err(|e| wasm_error!(e))?;
+  Ok(maybe_profile)
+}
@@ -0,0 +1,92 @@
+use hdk::prelude::*;
+use holo_hash::{AgentPubKeyB64, ActionHashB64};
+use std::collections::BTreeMap;
+use zome_utils::*;
+
+use where_integrity::*;
+use crate::here::*;
+
+
+/// An emoji reaction on a Here
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReactInput {
+  /// Session link of any version of the Here
+  pub here_link_ah: ActionHashB64,
+  pub emoji: String,
+}
+
+/// Agents who reacted to a Here with an emoji
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReactionOutput {
+  pub emoji: String,
+  pub agents: Vec<AgentPubKeyB64>,
+}
+
+
+/// React to a Here with an emoji. Reactions are keyed on the Here's identity so that they follow its updates.
+#[hdk_extern]
+fn react_to_here(input: ReactInput) -> ExternResult<()> {
+  let me = agent_info()?.agent_latest_pubkey;
+  let here_id = get_here_id(input.here_link_ah.clone().into())?;
+  if !get_my_reaction_links(here_id.clone(), &input.emoji, &me)?.is_empty() {
+    return zome_error!("Already reacted with {}", input.emoji);
+  }
+  create_link(here_id, me, WhereLinkType::Reactions, LinkTag::new(input.emoji.into_bytes()))?;
+  Ok(())
+}
+
+
+/// Remove our reaction with an emoji on a Here
+#[hdk_extern]
+fn unreact_to_here(input: ReactInput) -> ExternResult<()> {
+  let me = agent_info()?.agent_latest_pubkey;
+  let here_id = get_here_id(input.here_link_ah.into())?;
+  for link in get_my_reaction_links(here_id, &input.emoji, &me)? {
+    delete_link(link.create_link_hash)?;
+  }
+  Ok(())
+}
+
+
+/// Reactions on a Here with the agents who made them, given the session link of any of its versions
+#[hdk_extern]
+fn get_here_reactions(here_link_ah: ActionHashB64) -> ExternResult<Vec<ReactionOutput>> {
+  get_reactions_of(get_here_id(here_link_ah.into())?)
+}
+
+
+/// Reactions on a Here grouped by emoji, given its identity
+fn get_reactions_of(here_id: ActionHash) -> ExternResult<Vec<ReactionOutput>> {
+  let links = get_links(here_id, WhereLinkType::Reactions, None)?;
+  let mut reactions: Vec<ReactionOutput> = Vec::new();
+  for link in links {
+    let Ok(emoji) = String::from_utf8(link.tag.into_inner())
+      else {continue};
+    let agent: AgentPubKeyB64 = link.author.into();
+    match reactions.iter_mut().find(|reaction| reaction.emoji == emoji) {
+      Some(reaction) => if !reaction.agents.contains(&agent) { reaction.agents.push(agent) },
+      None => reactions.push(ReactionOutput {emoji, agents: vec![agent]}),
+    }
+  }
+  Ok(reactions)
+}
+
+
+/// Number of agents who reacted with each emoji on a Here, given its identity
+pub fn get_reaction_counts(here_id: ActionHash) -> ExternResult<BTreeMap<String, u32>> {
+  let counts = get_reactions_of(here_id)?.into_iter()
+    .map(|reaction| (reaction.emoji, reaction.agents.len() as u32))
+    .collect();
+  Ok(counts)
+}
+
+
+/// Our reaction links with an emoji on a Here, given its identity
+fn get_my_reaction_links(here_id: ActionHash, emoji: &str, me: &AgentPubKey) -> ExternResult<Vec<Link>> {
+  let tag = LinkTag::new(emoji.as_bytes().to_vec());
+  let links = get_links(here_id, WhereLinkType::Reactions, Some(tag.clone()))?;
+  // Tag filtering is by prefix
+  Ok(links.into_iter().filter(|link| link.tag == tag && &link.author == me).collect())
+}
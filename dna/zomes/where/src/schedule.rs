@@ -0,0 +1,169 @@
+use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use zome_utils::*;
+
+use where_integrity::*;
+use crate::placement_session::*;
+
+
+/// How often online agents check for due sessions
+pub const SCHEDULED_SESSIONS_CRON: &'static str = "0 */10 * * * * *";
+
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSessionScheduleInput {
+  pub space_eh: EntryHashB64,
+  pub period: SchedulePeriod,
+  pub starts_at: Timestamp,
+  pub name: String,
+}
+
+
+/// Create sessions on a space every day or week, starting at `starts_at`.
+/// The nth occurrence gets the index `first_index + n`: when a session already has that index,
+/// e.g. one created with `create_next_session`, the occurrence is skipped.
+#[hdk_extern]
+fn create_session_schedule(input: CreateSessionScheduleInput) -> ExternResult<EntryHashB64> {
+  let space_eh: EntryHash = input.space_eh.clone().into();
+  is_valid_space(space_eh.clone())?;
+  let schedule = SessionSchedule {
+    space_eh: input.space_eh,
+    period: input.period,
+    starts_at: input.starts_at,
+    name: input.name,
+    first_index: get_next_session_index(space_eh.clone())?,
+  };
+  let schedule_eh = hash_entry(schedule.clone())?;
+  create_entry(WhereEntry::SessionSchedule(schedule))?;
+  create_link(space_eh, schedule_eh.clone(), WhereLinkType::Schedules, LinkTag::from(()))?;
+  Ok(schedule_eh.into())
+}
+
+
+///
+#[hdk_extern]
+fn get_space_schedules(space_eh: EntryHashB64) -> ExternResult<Vec<(EntryHashB64, SessionSchedule)>> {
+  let links = get_links(space_eh, WhereLinkType::Schedules, None)?;
+  let mut schedules = Vec::with_capacity(links.len());
+  for link in links {
+    let Some(schedule_eh) = link.target.into_entry_hash()
+      else {continue};
+    let schedule = get_typed_from_eh::<SessionSchedule>(schedule_eh.clone())?;
+    schedules.push((schedule_eh.into(), schedule));
+  }
+  Ok(schedules)
+}
+
+
+/// Stop creating sessions for a schedule
+#[hdk_extern]
+fn delete_session_schedule(schedule_eh: EntryHashB64) -> ExternResult<()> {
+  let schedule = get_typed_from_eh::<SessionSchedule>(schedule_eh.clone().into())?;
+  let target = AnyLinkableHash::from(EntryHash::from(schedule_eh));
+  for link in get_links(schedule.space_eh, WhereLinkType::Schedules, None)? {
+    if link.target == target {
+      delete_link(link.create_link_hash)?;
+    }
+  }
+  Ok(())
+}
+
+
+/// Scheduled callback: create the current session of the schedules of our spaces if it does not exist yet
+#[hdk_extern(infallible)]
+fn create_scheduled_sessions(_: Option<Schedule>) -> Option<Schedule> {
+  if let Err(e) = create_due_sessions() {
+    debug!("create_scheduled_sessions() failed: {:?}", e);
+  }
+  Some(Schedule::Persisted(SCHEDULED_SESSIONS_CRON.to_string()))
+}
+
+
+/// A schedule that fails, e.g. because its entry can't be fetched, doesn't stop the others
+fn create_due_sessions() -> ExternResult<()> {
+  let now = sys_time()?;
+  for space_eh in get_my_spaces()? {
+    for link in get_links(space_eh, WhereLinkType::Schedules, None)? {
+      let Some(schedule_eh) = link.target.into_entry_hash()
+        else {continue};
+      let result = get_typed_from_eh::<SessionSchedule>(schedule_eh.clone())
+        .and_then(|schedule| create_due_session(&schedule, now));
+      if let Err(e) = result {
+        debug!("create_due_sessions(): Skipping schedule {}: {:?}", schedule_eh, e);
+      }
+    }
+  }
+  Ok(())
+}
+
+
+/// Spaces we take part in, from our source chain: those we created a schedule or a session on,
+/// or placed a Here on, except the ones we hid
+fn get_my_spaces() -> ExternResult<Vec<EntryHash>> {
+  let my_entries = |entry_type: WhereEntryTypes| -> ExternResult<Vec<Record>> {
+    query(ChainQueryFilter::new().entry_type(entry_type.try_into()?).include_entries(true))
+  };
+  let mut spaces: Vec<EntryHash> = Vec::new();
+  for record in my_entries(WhereEntryTypes::SessionSchedule)? {
+    if let Ok(Some(schedule)) = record.entry().to_app_option::<SessionSchedule>() {
+      spaces.push(schedule.space_eh.into());
+    }
+  }
+  for record in my_entries(WhereEntryTypes::PlacementSession)? {
+    if let Ok(Some(session)) = record.entry().to_app_option::<PlacementSession>() {
+      spaces.push(session.space_eh.into());
+    }
+  }
+  let mut session_ehs: Vec<EntryHash> = my_entries(WhereEntryTypes::Here)?
+    .into_iter()
+    .filter_map(|record| record.entry().to_app_option::<Here>().ok().flatten())
+    .map(|here| here.session_eh.into())
+    .collect();
+  session_ehs.sort();
+  session_ehs.dedup();
+  for session_eh in session_ehs {
+    if let Ok(Some(session)) = get_session_from_eh(session_eh.into()) {
+      spaces.push(session.space_eh.into());
+    }
+  }
+  let hidden: Vec<EntryHash> = get_links(agent_info()?.agent_latest_pubkey, WhereLinkType::Hide, None)?
+    .into_iter()
+    .filter_map(|link| link.target.into_entry_hash())
+    .collect();
+  spaces.sort();
+  spaces.dedup();
+  spaces.retain(|space_eh| !hidden.contains(space_eh));
+  Ok(spaces)
+}
+
+
+/// The session of an occurrence is fully determined by the schedule, so agents creating it
+/// concurrently create the same entry.
+/// A session already at the occurrence's index, scheduled or not, is kept.
+fn create_due_session(schedule: &SessionSchedule, now: Timestamp) -> ExternResult<()> {
+  let elapsed = now.as_micros() - schedule.starts_at.as_micros();
+  if elapsed < 0 {
+    return Ok(());
+  }
+  let period = schedule.period.as_micros();
+  let occurrence = elapsed / period;
+  let index = schedule.first_index + occurrence as u32;
+  let get_input = GetSessionInput {space_eh: schedule.space_eh.clone(), index};
+  if get_session(get_input)?.is_some() {
+    return Ok(());
+  }
+  let opens_at = schedule.starts_at.as_micros() + occurrence * period;
+  let session = PlacementSession {
+    name: format!("{} {}", schedule.name, occurrence + 1),
+    index,
+    space_eh: schedule.space_eh.clone(),
+    maybe_opens_at: Some(Timestamp::from_micros(opens_at)),
+    maybe_closes_at: Some(Timestamp::from_micros(opens_at + period)),
+    maybe_vote_budget: None,
+  };
+  create_session_entry(session)?;
+  Ok(())
+}
+
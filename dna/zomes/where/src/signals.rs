@@ -2,7 +2,10 @@ use hdk::prelude::*;
 use holo_hash::{EntryHashB64, AgentPubKeyB64, ActionHashB64};
 
 use where_integrity::*;
-use crate::here::*;
+use crate::{
+    here::*,
+    comments::*,
+};
 
 
 ///
@@ -17,6 +20,7 @@ pub enum Message {
     DeleteHere((EntryHashB64, ActionHashB64)), /// sessionEh, hereLinkHh
     UpdateHere((u32, ActionHashB64, Here)),    ///[index, newLinkAh, newHereEntry]}
     NewSession((EntryHashB64, PlacementSession)),
+    NewComment(CommentOutput),
     /// - with entry hash of entries
     NewSpace(EntryHashB64),
     NewTemplate(EntryHashB64),
@@ -46,7 +50,7 @@ impl SignalPayload {
 fn recv_remote_signal(signal: ExternIO) -> ExternResult<()> {
     let sig: SignalPayload = signal.decode().unwrap();
     debug!("Received signal from {:?}", sig.from);
-    Ok(emit_signal(&sig)?)
+    emit_signal(&sig)
 }
 
 /// Input to the notify call
@@ -0,0 +1,61 @@
+use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use zome_utils::*;
+
+use where_integrity::*;
+use crate::{
+  here::*,
+  placement_session::*,
+};
+
+
+/// Freeze the current Heres of a session into a snapshot and close the session.
+/// Only the session's creator can do it. Closure is advisory, see `is_session_closed()`.
+#[hdk_extern]
+fn snapshot_session(session_eh: EntryHashB64) -> ExternResult<EntryHashB64> {
+  if get_session_from_eh(session_eh.clone())?.is_none() {
+    return zome_error!("Session not found");
+  }
+  let Some(session_ah) = get_my_session_ah(session_eh.clone().into())?
+    else { return zome_error!("Only the session's creator can snapshot it") };
+  if is_session_closed(session_eh.clone().into())? {
+    return zome_error!("Session already has a snapshot");
+  }
+  let heres = get_heres(session_eh.clone())?.into_iter()
+    .map(|here| SnapshotHere {
+      value: here.entry.value,
+      meta: here.entry.meta,
+      author: here.author,
+      link_ah: here.link_ah,
+    })
+    .collect();
+  let snapshot = SessionSnapshot {session_eh: session_eh.clone(), session_ah: session_ah.into(), heres};
+  let snapshot_eh = hash_entry(snapshot.clone())?;
+  create_entry(WhereEntry::SessionSnapshot(snapshot))?;
+  create_link(EntryHash::from(session_eh), snapshot_eh.clone(), WhereLinkType::Snapshot, LinkTag::from(()))?;
+  Ok(snapshot_eh.into())
+}
+
+
+/// The snapshot of a session. If several agents took one concurrently, the earliest wins.
+#[hdk_extern]
+fn get_snapshot(session_eh: EntryHashB64) -> ExternResult<Option<SessionSnapshot>> {
+  let mut links = get_links(session_eh, WhereLinkType::Snapshot, None)?;
+  links.sort_by_key(|link| link.timestamp);
+  let Some(link) = links.into_iter().next()
+    else { return Ok(None) };
+  let Some(snapshot_eh) = link.target.into_entry_hash()
+    else { return Ok(None) };
+  let snapshot = get_typed_from_eh::<SessionSnapshot>(snapshot_eh)?;
+  Ok(Some(snapshot))
+}
+
+
+/// A session is closed once it has a snapshot.
+/// Closure is advisory: validation can't check that a session has no snapshot link, as link lookups
+/// are not deterministic, so it still accepts Heres written to a closed session by other clients.
+/// The snapshot is the reference for a closed session. To reject writes in validation, set `maybe_closes_at` on the session.
+pub fn is_session_closed(session_eh: EntryHash) -> ExternResult<bool> {
+  let links = get_links(session_eh, WhereLinkType::Snapshot, None)?;
+  Ok(!links.is_empty())
+}
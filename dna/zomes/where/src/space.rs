@@ -0,0 +1,104 @@
+use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use zome_utils::*;
+
+use space_types::*;
+use crate::call_playset_zome;
+use crate::geometry::*;
+use crate::placement_session::*;
+use crate::here::*;
+
+
+/// warning: partial duplicate of playset_integrity::Space
+/// Only holds the fields this zome needs, the other fields are ignored when decoding.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpaceInfo {
+  pub name: String,
+  #[serde(default)]
+  pub surface: String,
+  #[serde(default)]
+  pub zones: Vec<Zone>,
+  #[serde(default)]
+  pub maybe_coordinate_system: Option<CoordinateSystem>,
+}
+
+impl SpaceInfo {
+  pub fn coordinate_system(&self) -> CoordinateSystem {
+    self.maybe_coordinate_system.unwrap_or(CoordinateSystem::Cartesian)
+  }
+
+  /// Corners of the area Heres can be placed on: the whole globe on geo spaces,
+  /// the surface's `size` on cartesian spaces, if it has one
+  pub fn bounds(&self) -> Option<(Coord, Coord)> {
+    match self.coordinate_system() {
+      CoordinateSystem::Geo => Some((Coord {x: -180.0, y: -90.0}, Coord {x: 180.0, y: 90.0})),
+      CoordinateSystem::Cartesian => {
+        let surface: serde_json::Value = serde_json::from_str(&self.surface).ok()?;
+        let size = serde_json::from_value::<Coord>(surface.get("size")?.clone()).ok()?;
+        Some((Coord {x: 0.0, y: 0.0}, size))
+      },
+    }
+  }
+}
+
+
+/// warning: duplicate of playset::SubSpaceOutput
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SubSpaceOutput {
+  pub space_eh: EntryHashB64,
+  pub attachment: SpaceAttachment,
+}
+
+/// Heres in the current session of a sub-space
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SubSpaceRollup {
+  pub space_eh: EntryHashB64,
+  pub attachment: SpaceAttachment,
+  /// Session with the highest index, if any
+  pub maybe_session_eh: Option<EntryHashB64>,
+  pub here_count: u32,
+}
+
+
+/// Get a Space from the playset zome
+pub fn get_space_info(space_eh: EntryHashB64) -> ExternResult<SpaceInfo> {
+  let res = call_playset_zome("get_space", space_eh.clone())?;
+  let maybe_space: Option<SpaceInfo> = decode_response(res)?;
+  let Some(space) = maybe_space
+    else { return zome_error!("Space not found: {}", space_eh) };
+  Ok(space)
+}
+
+
+/// Get the Space of a session from the playset zome
+pub fn get_session_space_info(session_eh: EntryHashB64) -> ExternResult<SpaceInfo> {
+  let Some(session) = get_session_from_eh(session_eh)?
+    else { return zome_error!("Session not found") };
+  get_space_info(session.space_eh)
+}
+
+
+/// Number of Heres in the current session of each sub-space of a space
+#[hdk_extern]
+fn get_sub_space_rollup(space_eh: EntryHashB64) -> ExternResult<Vec<SubSpaceRollup>> {
+  let res = call_playset_zome("get_sub_spaces", space_eh)?;
+  let sub_spaces: Vec<SubSpaceOutput> = decode_response(res)?;
+  let mut rollup = Vec::with_capacity(sub_spaces.len());
+  for sub_space in sub_spaces {
+    let next_index = get_next_session_index(sub_space.space_eh.clone().into())?;
+    let maybe_session_eh = if next_index == 0 {
+      None
+    } else {
+      get_session(GetSessionInput {space_eh: sub_space.space_eh.clone(), index: next_index - 1})?
+    };
+    let here_count = match &maybe_session_eh {
+      Some(session_eh) => get_heres(session_eh.clone())?.len() as u32,
+      None => 0,
+    };
+    rollup.push(SubSpaceRollup {space_eh: sub_space.space_eh, attachment: sub_space.attachment, maybe_session_eh, here_count});
+  }
+  Ok(rollup)
+}
@@ -0,0 +1,162 @@
+use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use std::collections::{BTreeMap, BTreeSet};
+
+use where_integrity::*;
+use crate::{
+  geometry::*,
+  here::*,
+  space::*,
+};
+
+
+/// Meta key of the emoji of a Here
+pub const EMOJI_META_KEY: &'static str = "emoji";
+/// Meta key of the tag of a Here
+pub const TAG_META_KEY: &'static str = "tag";
+
+
+/// Aggregates of the Heres of a session
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStats {
+  pub coordinate_system: CoordinateSystem,
+  pub count: u32,
+  /// Mean position of the Heres with a position
+  pub maybe_centroid: Option<Coord>,
+  /// Root mean square distance to the centroid, in km on geo spaces
+  pub maybe_spread: Option<f64>,
+  pub zone_counts: BTreeMap<String, u32>,
+  pub emoji_counts: BTreeMap<String, u32>,
+  pub tag_counts: BTreeMap<String, u32>,
+}
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareSessionsInput {
+  pub from_session_eh: EntryHashB64,
+  pub to_session_eh: EntryHashB64,
+}
+
+/// How the aggregates shifted from one session to another
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatsComparison {
+  pub from: SessionStats,
+  pub to: SessionStats,
+  pub count_delta: i64,
+  /// Distance between the two centroids
+  pub maybe_centroid_shift: Option<f64>,
+  pub maybe_spread_delta: Option<f64>,
+  pub zone_deltas: BTreeMap<String, i64>,
+  pub emoji_deltas: BTreeMap<String, i64>,
+  pub tag_deltas: BTreeMap<String, i64>,
+}
+
+
+/// Aggregates computed from the values and meta of the Heres of a session
+#[hdk_extern]
+fn get_session_stats(session_eh: EntryHashB64) -> ExternResult<SessionStats> {
+  get_session_stats_inner(session_eh)
+}
+
+
+///
+pub fn get_session_stats_inner(session_eh: EntryHashB64) -> ExternResult<SessionStats> {
+  let space = get_session_space_info(session_eh.clone())?;
+  let system = space.coordinate_system();
+  let heres = get_heres(session_eh)?;
+  let mut zone_counts: BTreeMap<String, u32> = space.zones.iter()
+    .map(|zone| (zone.name.clone(), 0))
+    .collect();
+  let mut emoji_counts = BTreeMap::new();
+  let mut tag_counts = BTreeMap::new();
+  let mut coords = Vec::new();
+  for here in heres.iter() {
+    if let Some(emoji) = get_meta_string(&here.entry, EMOJI_META_KEY) {
+      *emoji_counts.entry(emoji).or_insert(0) += 1;
+    }
+    if let Some(tag) = get_meta_string(&here.entry, TAG_META_KEY) {
+      *tag_counts.entry(tag).or_insert(0) += 1;
+    }
+    let Some(coord) = parse_position(&here.entry.value, system)
+      else {continue};
+    for zone in space.zones.iter().filter(|zone| zone.shape.contains(coord.x, coord.y)) {
+      *zone_counts.entry(zone.name.clone()).or_insert(0) += 1;
+    }
+    coords.push(coord);
+  }
+  let maybe_centroid = if coords.is_empty() {
+    None
+  } else {
+    let n = coords.len() as f64;
+    Some(Coord {
+      x: coords.iter().map(|c| c.x).sum::<f64>() / n,
+      y: coords.iter().map(|c| c.y).sum::<f64>() / n,
+    })
+  };
+  let maybe_spread = maybe_centroid.map(|centroid| {
+    let sum_sq: f64 = coords.iter().map(|c| distance(system, centroid, *c).powi(2)).sum();
+    (sum_sq / coords.len() as f64).sqrt()
+  });
+  Ok(SessionStats {
+    coordinate_system: system,
+    count: heres.len() as u32,
+    maybe_centroid,
+    maybe_spread,
+    zone_counts,
+    emoji_counts,
+    tag_counts,
+  })
+}
+
+
+/// Aggregates of two sessions and how they shifted from the first to the second
+#[hdk_extern]
+fn compare_sessions(input: CompareSessionsInput) -> ExternResult<SessionStatsComparison> {
+  let from = get_session_stats_inner(input.from_session_eh)?;
+  let to = get_session_stats_inner(input.to_session_eh)?;
+  /// Positions are only comparable within the same coordinate system
+  let maybe_centroid_shift = match (from.maybe_centroid, to.maybe_centroid) {
+    (Some(a), Some(b)) if from.coordinate_system == to.coordinate_system => Some(distance(to.coordinate_system, a, b)),
+    _ => None,
+  };
+  let maybe_spread_delta = match (from.maybe_spread, to.maybe_spread) {
+    (Some(a), Some(b)) if from.coordinate_system == to.coordinate_system => Some(b - a),
+    _ => None,
+  };
+  Ok(SessionStatsComparison {
+    count_delta: to.count as i64 - from.count as i64,
+    maybe_centroid_shift,
+    maybe_spread_delta,
+    zone_deltas: count_deltas(&from.zone_counts, &to.zone_counts),
+    emoji_deltas: count_deltas(&from.emoji_counts, &to.emoji_counts),
+    tag_deltas: count_deltas(&from.tag_counts, &to.tag_counts),
+    from,
+    to,
+  })
+}
+
+
+/// Read a meta value of a Here. Values are JSON-encoded by the UI, raw strings are accepted as is.
+pub fn get_meta_string(here: &Here, key: &str) -> Option<String> {
+  let raw = here.meta.get(key)?;
+  let value = serde_json::from_str::<String>(raw).unwrap_or_else(|_| raw.clone());
+  if value.is_empty() {
+    return None;
+  }
+  Some(value)
+}
+
+
+/// Difference of counts for every key present in either map
+fn count_deltas(from: &BTreeMap<String, u32>, to: &BTreeMap<String, u32>) -> BTreeMap<String, i64> {
+  let keys: BTreeSet<&String> = from.keys().chain(to.keys()).collect();
+  keys.into_iter()
+    .map(|key| {
+      let delta = *to.get(key).unwrap_or(&0) as i64 - *from.get(key).unwrap_or(&0) as i64;
+      (key.clone(), delta)
+    })
+    .collect()
+}
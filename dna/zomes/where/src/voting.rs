@@ -0,0 +1,101 @@
+use hdk::prelude::*;
+use holo_hash::EntryHashB64;
+use std::collections::BTreeMap;
+use zome_utils::*;
+
+use where_integrity::*;
+use crate::{
+  placement_session::*,
+  stats::*,
+};
+
+
+/// Number of votes for a zone or an item
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TallyEntry {
+  pub name: String,
+  pub votes: u32,
+}
+
+/// Votes of a session ranked by zone and by item (the `tag` meta of the Heres), most voted first
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoteTally {
+  pub total: u32,
+  pub zones: Vec<TallyEntry>,
+  pub items: Vec<TallyEntry>,
+}
+
+
+/// Fail if we already used our whole vote budget in a session in voting mode.
+/// Validation enforces the same rule, this only gives an early and readable error.
+pub fn check_vote_budget(session_eh: EntryHash) -> ExternResult<()> {
+  let Some(session) = get_session_from_eh(session_eh.clone().into())?
+    else { return zome_error!("Session not found") };
+  let Some(budget) = session.maybe_vote_budget
+    else { return Ok(()) };
+  let remaining = get_remaining_votes_inner(session_eh, budget)?;
+  if remaining == 0 {
+    return zome_error!("Vote budget of {} exhausted", budget);
+  }
+  Ok(())
+}
+
+
+/// Number of votes we can still place in a session, or None if the session is not in voting mode
+#[hdk_extern]
+fn get_remaining_votes(session_eh: EntryHashB64) -> ExternResult<Option<u32>> {
+  let Some(session) = get_session_from_eh(session_eh.clone())?
+    else { return zome_error!("Session not found") };
+  let Some(budget) = session.maybe_vote_budget
+    else { return Ok(None) };
+  Ok(Some(get_remaining_votes_inner(session_eh.into(), budget)?))
+}
+
+
+/// Rank the zones and items of a session by number of votes
+#[hdk_extern]
+fn get_vote_tally(session_eh: EntryHashB64) -> ExternResult<VoteTally> {
+  let stats = get_session_stats_inner(session_eh)?;
+  Ok(VoteTally {
+    total: stats.count,
+    zones: rank(stats.zone_counts),
+    items: rank(stats.tag_counts),
+  })
+}
+
+
+/// Count our live session links on our own source chain, the way validation does,
+/// so the result does not depend on what the DHT has seen yet
+fn get_remaining_votes_inner(session_eh: EntryHash, budget: u32) -> ExternResult<u32> {
+  let scoped = ScopedLinkType::try_from(WhereLinkType::All)?;
+  let base = AnyLinkableHash::from(session_eh);
+  let filter = ChainQueryFilter::new()
+    .action_type(ActionType::CreateLink)
+    .action_type(ActionType::DeleteLink);
+  let mut votes: Vec<ActionHash> = Vec::new();
+  let mut deleted: Vec<ActionHash> = Vec::new();
+  for record in query(filter)? {
+    match record.action() {
+      Action::CreateLink(create) if create.zome_index == scoped.zome_index && create.link_type == scoped.zome_type
+        && create.base_address == base => {
+        votes.push(record.action_address().clone());
+      },
+      Action::DeleteLink(delete) => deleted.push(delete.link_add_address.clone()),
+      _ => {},
+    }
+  }
+  let used = votes.iter().filter(|ah| !deleted.contains(ah)).count() as u32;
+  Ok(budget.saturating_sub(used))
+}
+
+
+/// Sort by votes, then by name
+fn rank(counts: BTreeMap<String, u32>) -> Vec<TallyEntry> {
+  let mut entries: Vec<TallyEntry> = counts.into_iter()
+    .map(|(name, votes)| TallyEntry {name, votes})
+    .collect();
+  entries.sort_by(|a, b| b.votes.cmp(&a.votes).then_with(|| a.name.cmp(&b.name)));
+  entries
+}
@@ -0,0 +1,57 @@
+use hdk::prelude::*;
+use holo_hash::{EntryHashB64, AgentPubKeyB64};
+
+use crate::{
+  geometry::*,
+  here::*,
+  space::*,
+};
+
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneOccupants {
+  pub zone_name: String,
+  pub agents: Vec<AgentPubKeyB64>,
+}
+
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionZonesOutput {
+  pub zones: Vec<ZoneOccupants>,
+  pub outside: Vec<AgentPubKeyB64>, // agents with a Here in no zone
+}
+
+
+/// Agents placed in each zone of the session's space.
+/// An agent appears in every zone containing one of its Heres.
+#[hdk_extern]
+fn get_session_zones(session_eh: EntryHashB64) -> ExternResult<SessionZonesOutput> {
+  let space = get_session_space_info(session_eh.clone())?;
+  let system = space.coordinate_system();
+  let heres = get_heres(session_eh)?;
+  let mut zones: Vec<ZoneOccupants> = space.zones.iter()
+    .map(|zone| ZoneOccupants {zone_name: zone.name.clone(), agents: vec![]})
+    .collect();
+  let mut outside = Vec::new();
+  for here in heres {
+    let Some(coord) = parse_position(&here.entry.value, system)
+      else {continue};
+    let mut in_zone = false;
+    for (zone, occupants) in space.zones.iter().zip(zones.iter_mut()) {
+      if !zone.shape.contains(coord.x, coord.y) {
+        continue;
+      }
+      in_zone = true;
+      if !occupants.agents.contains(&here.author) {
+        occupants.agents.push(here.author.clone());
+      }
+    }
+    if !in_zone && !outside.contains(&here.author) {
+      outside.push(here.author);
+    }
+  }
+  Ok(SessionZonesOutput {zones, outside})
+}
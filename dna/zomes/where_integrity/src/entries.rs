@@ -1,5 +1,7 @@
 use hdi::prelude::*;
-use holo_hash::EntryHashB64;
+use anchors::*;
+pub use space_types::CoordinateSystem;
+use holo_hash::{EntryHashB64, AgentPubKeyB64, ActionHashB64};
 use std::collections::BTreeMap;
 
 #[hdk_entry_helper]
@@ -9,6 +11,171 @@ pub struct PlacementSession {
     pub name: String,
     pub index: u32,
     pub space_eh: EntryHashB64,
+    /// Heres created before this time are rejected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maybe_opens_at: Option<Timestamp>,
+    /// Heres created after this time are rejected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maybe_closes_at: Option<Timestamp>,
+    /// Voting mode: each Here is a vote and an agent can have at most this many Heres in the session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maybe_vote_budget: Option<u32>,
+}
+
+
+/// Comment on a Here, possibly replying to another comment on the same Here
+#[hdk_entry_helper]
+#[derive(Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HereComment {
+    /// Identity of the Here: ActionHash of the session link of its first version
+    pub here_link_ah: ActionHashB64,
+    pub text: String,
+    /// ActionHash of the comment replied to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maybe_reply_to: Option<ActionHashB64>,
+}
+
+
+/// Scale then translate applied to the cartesian positions of a session's Heres
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HereTransform {
+    pub scale_x: f64,
+    pub scale_y: f64,
+    pub translate_x: f64,
+    pub translate_y: f64,
+}
+
+impl HereTransform {
+    pub fn identity() -> Self {
+        HereTransform {scale_x: 1.0, scale_y: 1.0, translate_x: 0.0, translate_y: 0.0}
+    }
+
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        (x * self.scale_x + self.translate_x, y * self.scale_y + self.translate_y)
+    }
+
+    /// Transform applying `self` then `next`
+    pub fn then(&self, next: &HereTransform) -> Self {
+        HereTransform {
+            scale_x: self.scale_x * next.scale_x,
+            scale_y: self.scale_y * next.scale_y,
+            translate_x: self.translate_x * next.scale_x + next.translate_x,
+            translate_y: self.translate_y * next.scale_y + next.translate_y,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        [self.scale_x, self.scale_y, self.translate_x, self.translate_y].iter().all(|v| v.is_finite())
+            && self.scale_x != 0.0 && self.scale_y != 0.0
+    }
+}
+
+
+/// Overlay migrating the positions of the Heres of a session, e.g. after its space's surface was resized.
+/// The Here entries are left untouched.
+#[hdk_entry_helper]
+#[derive(Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTransform {
+    pub session_eh: EntryHashB64,
+    /// Creation of the session by the transform's author: only a session's creator can migrate it
+    pub session_ah: ActionHashB64,
+    pub transform: HereTransform,
+}
+
+
+/// Holochain Resource Locator: DNA and hash of a record, possibly of another happ
+pub type Hrl = (DnaHash, AnyDhtHash);
+
+/// Root path of the anchors of attached HRLs
+pub const ATTACHABLES_ROOT_PATH: &'static str = "attachables";
+
+/// HRL attached to a Here, carried in the tag of the attachment links.
+/// Same shape as the HrlWithContext of the We applets, with the context JSON-serialized.
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HrlWithContext {
+    pub hrl: Hrl,
+    #[serde(default)]
+    pub context: Option<String>, // Json
+}
+
+impl HrlWithContext {
+    pub fn to_tag(&self) -> ExternResult<LinkTag> {
+        let sb = SerializedBytes::try_from(self.clone()).map_err(|e| wasm_error!(e))?;
+        Ok(LinkTag::new(sb.bytes().to_vec()))
+    }
+
+    pub fn from_tag(tag: LinkTag) -> Option<Self> {
+        HrlWithContext::try_from(SerializedBytes::from(UnsafeBytes::from(tag.into_inner()))).ok()
+    }
+}
+
+/// Anchor of an HRL, base of the links to the Heres it is attached to.
+/// The path is never created, it only provides a hash.
+pub fn hrl_anchor_hash(hrl: &Hrl) -> ExternResult<EntryHash> {
+    let components = vec![
+        AnchorComponent::from(ATTACHABLES_ROOT_PATH),
+        AnchorComponent::from(hrl.0.get_raw_39().to_vec()),
+        AnchorComponent::from(hrl.1.get_raw_39().to_vec()),
+    ];
+    AnchorPath::from(components).path_entry_hash()
+}
+
+
+/// Frozen copy of the Heres of a session.
+/// A session with a snapshot is closed, but only by the coordinator: see `is_session_closed` in the where zome.
+#[hdk_entry_helper]
+#[derive(Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSnapshot {
+    pub session_eh: EntryHashB64,
+    /// Creation of the session by the snapshot's author: only a session's creator can close it
+    pub session_ah: ActionHashB64,
+    pub heres: Vec<SnapshotHere>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotHere {
+    pub value: String,
+    pub meta: BTreeMap<String, String>,
+    pub author: AgentPubKeyB64,
+    pub link_ah: ActionHashB64,
+}
+
+
+/// Recurring creation of sessions on a space
+#[hdk_entry_helper]
+#[derive(Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSchedule {
+    pub space_eh: EntryHashB64,
+    pub period: SchedulePeriod,
+    /// Opening time of the first session
+    pub starts_at: Timestamp,
+    /// Sessions are named "<name> <occurrence number>"
+    pub name: String,
+    /// Index of the first session, the nth occurrence gets `first_index + n`
+    pub first_index: u32,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SchedulePeriod {
+    Daily,
+    Weekly,
+}
+
+impl SchedulePeriod {
+    pub fn as_micros(&self) -> i64 {
+        match self {
+            SchedulePeriod::Daily => 24 * 3600 * 1_000_000,
+            SchedulePeriod::Weekly => 7 * 24 * 3600 * 1_000_000,
+        }
+    }
 }
 
 
@@ -20,6 +187,97 @@ pub struct Here {
     pub value: String, // a location in some arbitrary space (Json encoded)
     pub session_eh: EntryHashB64,
     pub meta: BTreeMap<String, String>, // contextualized meaning of the value
+    /// The Here is ignored after this time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maybe_expires_at: Option<Timestamp>,
+    /// Session link of the first version of this Here, when it replaces a previous version.
+    /// Comments, reactions and attachments are keyed on it so they survive updates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maybe_origin_ah: Option<ActionHashB64>,
+}
+
+impl Here {
+    /// Stable identity of a Here across updates, given the ActionHash of its session link
+    pub fn here_id(&self, link_ah: &ActionHash) -> ActionHash {
+        self.maybe_origin_ah.clone().map(|ah| ah.into()).unwrap_or_else(|| link_ah.clone())
+    }
+}
+
+
+/// Tag of an Expiring link: the expiry time in microseconds, little endian
+pub fn expiry_tag(expires_at: Timestamp) -> LinkTag {
+    LinkTag::new(expires_at.as_micros().to_le_bytes().to_vec())
+}
+
+pub fn expiry_from_tag(tag: &LinkTag) -> Option<Timestamp> {
+    let bytes: [u8; 8] = tag.0.as_slice().try_into().ok()?;
+    Some(Timestamp::from_micros(i64::from_le_bytes(bytes)))
+}
+
+
+/// Summary of a Here stored in the tag of its session link, checked against the Here by validation
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HereSummary {
+    pub value: String,
+    pub meta: BTreeMap<String, String>,
+    pub maybe_expires_at: Option<Timestamp>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maybe_origin_ah: Option<ActionHashB64>,
+}
+
+/// Max size in bytes of a summary in a link tag
+pub const MAX_SUMMARY_TAG_SIZE: usize = 800;
+
+impl HereSummary {
+    pub fn of(here: &Here) -> Self {
+        HereSummary {
+            value: here.value.clone(),
+            meta: here.meta.clone(),
+            maybe_expires_at: here.maybe_expires_at,
+            maybe_origin_ah: here.maybe_origin_ah.clone(),
+        }
+    }
+
+    pub fn from_tag(tag: LinkTag) -> Option<Self> {
+        HereSummary::try_from(SerializedBytes::from(UnsafeBytes::from(tag.into_inner()))).ok()
+    }
+}
+
+
+/// Link tag of a session link: the Here summary, or empty if it is too large
+pub fn here_link_tag(here: &Here) -> ExternResult<LinkTag> {
+    let sb = SerializedBytes::try_from(HereSummary::of(here)).map_err(|e| wasm_error!(e))?;
+    let bytes = sb.bytes().to_vec();
+    if bytes.len() > MAX_SUMMARY_TAG_SIZE {
+        return Ok(LinkTag::from(()));
+    }
+    Ok(LinkTag::new(bytes))
+}
+
+
+/// Format of `Here.value` on geo spaces
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub struct GeoCoord {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl GeoCoord {
+    pub fn is_in_range(&self) -> bool {
+        self.lat >= -90.0 && self.lat <= 90.0 && self.lon >= -180.0 && self.lon <= 180.0
+    }
+}
+
+
+/// warning: partial duplicate of playset_integrity::Space, not an entry type of this zome
+/// Used to decode the fields of a Space needed by this zome, the other fields are ignored.
+#[hdk_entry_helper]
+#[derive(Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SpaceCoordinates {
+    #[serde(default)]
+    pub maybe_coordinate_system: Option<CoordinateSystem>,
 }
 
 
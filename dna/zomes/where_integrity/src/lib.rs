@@ -3,9 +3,16 @@
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 #![allow(unused_attributes)]
+#![allow(clippy::redundant_static_lifetimes)]
+#![allow(clippy::empty_docs)]
+#![allow(clippy::empty_line_after_doc_comments)]
+#![allow(clippy::empty_line_after_outer_attr)]
 
 mod entries;
+mod validate;
+
 pub use entries::*;
+pub use validate::*;
 
 
 ///-------------------------------------------------------------------------------------------------
@@ -29,6 +36,14 @@ pub enum WhereEntry {
     Here(Here),
     #[entry_def(required_validations = 2, visibility = "public")]
     PlacementSession(PlacementSession),
+    #[entry_def(required_validations = 2, visibility = "public")]
+    SessionSchedule(SessionSchedule),
+    #[entry_def(required_validations = 2, visibility = "public")]
+    SessionSnapshot(SessionSnapshot),
+    #[entry_def(required_validations = 2, visibility = "public")]
+    HereComment(HereComment),
+    #[entry_def(required_validations = 2, visibility = "public")]
+    SessionTransform(SessionTransform),
     //#[entry_def(required_validations = 2, visibility = "public")]
     //Playfield(Playfield),
 }
@@ -43,5 +58,16 @@ pub enum WhereEntry {
 #[derive(Serialize, Deserialize)]
 pub enum WhereLinkType {
     All,
-    Hide
+    Hide,
+    HereIndex, // spatial index cell -> Here, tagged with the session link's ActionHash
+    Carried, // new session -> session link of a Here of the previous session, tagged with the Here's author
+    Schedules, // space -> SessionSchedule
+    Snapshot, // session -> SessionSnapshot
+    Comments, // Here id (session link of its first version) -> HereComment create action
+    Reactions, // Here id -> reacting agent, tagged with the emoji
+    Transforms, // session -> SessionTransform
+    HereAttachments, // Here id -> HRL anchor, tagged with the HrlWithContext
+    AttachedHeres, // HRL anchor -> Here id, tagged with the HrlWithContext
+    HereUpdates, // session link of a Here's first version -> session link of a later version
+    Expiring, // author -> session link of one of its Heres with an expiry, tagged with the expiry time
 }
@@ -0,0 +1,386 @@
+use hdi::prelude::*;
+use holo_hash::{EntryHashB64, ActionHashB64};
+
+use crate::*;
+
+
+///
+#[hdk_extern]
+pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+  match op.flattened::<WhereEntry, WhereLinkType>()? {
+    FlatOp::StoreEntry(OpEntry::CreateEntry { app_entry, action }) => {
+      match app_entry {
+        WhereEntry::Here(here) => validate_here(here, action.timestamp, action.author),
+        WhereEntry::HereComment(comment) => validate_comment(comment),
+        WhereEntry::SessionSnapshot(snapshot) => validate_session_creator(snapshot.session_eh, snapshot.session_ah, &action.author),
+        WhereEntry::SessionTransform(session_transform) => {
+          if !session_transform.transform.is_valid() {
+            return Ok(ValidateCallbackResult::Invalid("Transform must be finite with non-zero scales".to_string()));
+          }
+          validate_session_creator(session_transform.session_eh, session_transform.session_ah, &action.author)
+        },
+        _ => Ok(ValidateCallbackResult::Valid),
+      }
+    },
+    FlatOp::StoreEntry(OpEntry::UpdateEntry { app_entry: WhereEntry::HereComment(comment), action, original_action_hash, .. }) => {
+      validate_comment_update(comment, action.author, original_action_hash)
+    },
+    FlatOp::StoreRecord(OpRecord::DeleteEntry { original_action_hash, action, .. }) => {
+      /// Entries can only be deleted by their author
+      let original = must_get_action(original_action_hash)?;
+      if original.action().author() != &action.author {
+        return Ok(ValidateCallbackResult::Invalid("Only the author can delete an entry".to_string()));
+      }
+      Ok(ValidateCallbackResult::Valid)
+    },
+    FlatOp::RegisterCreateLink { link_type, base_address, target_address, tag, action } => {
+      match link_type {
+        WhereLinkType::Carried => validate_carried_link(base_address, target_address, tag),
+        WhereLinkType::Snapshot => validate_snapshot_link(base_address, target_address, action),
+        WhereLinkType::Comments => validate_comment_link(base_address, target_address),
+        WhereLinkType::Reactions => validate_reaction_link(base_address, target_address, tag, action),
+        WhereLinkType::Transforms => validate_transform_link(base_address, target_address, action),
+        WhereLinkType::HereAttachments => validate_attachment_link(base_address, target_address, tag),
+        WhereLinkType::AttachedHeres => validate_attachment_link(target_address, base_address, tag),
+        WhereLinkType::HereUpdates => validate_here_update_link(base_address, target_address, action),
+        WhereLinkType::Expiring => validate_expiring_link(base_address, target_address, tag, action),
+        WhereLinkType::All => validate_all_link(base_address, target_address, tag, action),
+        _ => Ok(ValidateCallbackResult::Valid),
+      }
+    },
+    FlatOp::RegisterDeleteLink { link_type, original_action, action, .. } => {
+      match link_type {
+        WhereLinkType::Carried => {
+          /// Only the session creator or the Here's author can drop a carried Here
+          if action.author != original_action.author && original_action.tag.into_inner() != action.author.get_raw_39().to_vec() {
+            return Ok(ValidateCallbackResult::Invalid("Only the Here's author can discard a carried Here".to_string()));
+          }
+          Ok(ValidateCallbackResult::Valid)
+        },
+        WhereLinkType::Comments | WhereLinkType::Reactions | WhereLinkType::Expiring
+        | WhereLinkType::HereAttachments | WhereLinkType::AttachedHeres => {
+          if action.author != original_action.author {
+            return Ok(ValidateCallbackResult::Invalid("Only the link's author can remove it".to_string()));
+          }
+          Ok(ValidateCallbackResult::Valid)
+        },
+        _ => Ok(ValidateCallbackResult::Valid),
+      }
+    },
+    _ => Ok(ValidateCallbackResult::Valid),
+  }
+}
+
+
+/// A Here must be created while its session is open, and not be already expired.
+/// A Here on a geo space must be a position with latitude and longitude in range.
+/// A new version of a Here must be by the author of its first version.
+fn validate_here(here: Here, timestamp: Timestamp, author: AgentPubKey) -> ExternResult<ValidateCallbackResult> {
+  if let Some(origin_ah) = here.maybe_origin_ah.clone() {
+    let maybe_origin_link = must_get_here_link(ActionHash::from(origin_ah).into())?
+      .filter(|(_, origin)| origin.maybe_origin_ah.is_none());
+    let Some((origin_link, _)) = maybe_origin_link
+      else { return Ok(ValidateCallbackResult::Invalid("Origin must be the session link of a Here's first version".to_string())) };
+    if origin_link.author != author {
+      return Ok(ValidateCallbackResult::Invalid("Only the author of a Here can update it".to_string()));
+    }
+  }
+  let Ok(session) = PlacementSession::try_from(must_get_entry(here.session_eh.into())?.into_content())
+    else { return Ok(ValidateCallbackResult::Invalid("Here must be in a PlacementSession".to_string())) };
+  if session.maybe_opens_at.map(|opens_at| timestamp < opens_at).unwrap_or(false) {
+    return Ok(ValidateCallbackResult::Invalid("Session is not open yet".to_string()));
+  }
+  if session.maybe_closes_at.map(|closes_at| timestamp > closes_at).unwrap_or(false) {
+    return Ok(ValidateCallbackResult::Invalid("Session is closed".to_string()));
+  }
+  if here.maybe_expires_at.map(|expires_at| expires_at <= timestamp).unwrap_or(false) {
+    return Ok(ValidateCallbackResult::Invalid("Here is already expired".to_string()));
+  }
+  let Ok(space) = SpaceCoordinates::try_from(must_get_entry(session.space_eh.into())?.into_content())
+    else { return Ok(ValidateCallbackResult::Invalid("Session must be of a Space".to_string())) };
+  if space.maybe_coordinate_system == Some(CoordinateSystem::Geo) {
+    let Ok(coord) = serde_json::from_str::<GeoCoord>(&here.value)
+      else { return Ok(ValidateCallbackResult::Invalid("Here value on a geo space must be {\"lat\", \"lon\"}".to_string())) };
+    if !coord.is_in_range() {
+      return Ok(ValidateCallbackResult::Invalid(format!("Here position out of range: lat {} lon {}", coord.lat, coord.lon)));
+    }
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// A carried Here must be the session link of a Here of the previous session of the same space,
+/// tagged with the author of that Here
+fn validate_carried_link(base: AnyLinkableHash, target: AnyLinkableHash, tag: LinkTag) -> ExternResult<ValidateCallbackResult> {
+  let Some(session_eh) = base.into_entry_hash()
+    else { return Ok(ValidateCallbackResult::Invalid("Carried link must be from a session".to_string())) };
+  let Some((here_link, here)) = must_get_here_link(target)?
+    else { return Ok(ValidateCallbackResult::Invalid("Carried link must point to the session link of a Here".to_string())) };
+  if tag.into_inner() != here_link.author.get_raw_39().to_vec() {
+    return Ok(ValidateCallbackResult::Invalid("Carried link must be tagged with the Here's author".to_string()));
+  }
+  let Ok(session) = PlacementSession::try_from(must_get_entry(session_eh)?.into_content())
+    else { return Ok(ValidateCallbackResult::Invalid("Carried link must be from a PlacementSession".to_string())) };
+  let Ok(previous) = PlacementSession::try_from(must_get_entry(here.session_eh.into())?.into_content())
+    else { return Ok(ValidateCallbackResult::Invalid("Carried Here must be in a PlacementSession".to_string())) };
+  if previous.space_eh != session.space_eh || previous.index + 1 != session.index {
+    return Ok(ValidateCallbackResult::Invalid("Carried Here must be from the previous session of the same space".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// A snapshot link must point to a snapshot of its session and be made by the session's creator
+fn validate_snapshot_link(base: AnyLinkableHash, target: AnyLinkableHash, action: CreateLink) -> ExternResult<ValidateCallbackResult> {
+  let Some(snapshot_eh) = target.into_entry_hash()
+    else { return Ok(ValidateCallbackResult::Invalid("Snapshot link must point to a SessionSnapshot".to_string())) };
+  let Ok(snapshot) = SessionSnapshot::try_from(must_get_entry(snapshot_eh)?.into_content())
+    else { return Ok(ValidateCallbackResult::Invalid("Snapshot link must point to a SessionSnapshot".to_string())) };
+  if AnyLinkableHash::from(EntryHash::from(snapshot.session_eh.clone())) != base {
+    return Ok(ValidateCallbackResult::Invalid("Snapshot is of another session".to_string()));
+  }
+  validate_session_creator(snapshot.session_eh, snapshot.session_ah, &action.author)
+}
+
+
+/// A comment must not be empty and can only reply to a comment on the same Here
+fn validate_comment(comment: HereComment) -> ExternResult<ValidateCallbackResult> {
+  if comment.text.trim().is_empty() {
+    return Ok(ValidateCallbackResult::Invalid("Comment is empty".to_string()));
+  }
+  if let Some(reply_to) = comment.maybe_reply_to {
+    let replied_action = must_get_action(reply_to.into())?;
+    let Some(replied_eh) = replied_action.action().entry_hash()
+      else { return Ok(ValidateCallbackResult::Invalid("A reply must be to a comment".to_string())) };
+    let Ok(replied) = HereComment::try_from(must_get_entry(replied_eh.clone())?.into_content())
+      else { return Ok(ValidateCallbackResult::Invalid("A reply must be to a comment".to_string())) };
+    if replied.here_link_ah != comment.here_link_ah {
+      return Ok(ValidateCallbackResult::Invalid("A reply must be to a comment on the same Here".to_string()));
+    }
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// Only the author can edit a comment, and it stays on the same Here
+fn validate_comment_update(comment: HereComment, author: AgentPubKey, original_action_hash: ActionHash) -> ExternResult<ValidateCallbackResult> {
+  let original_action = must_get_action(original_action_hash)?;
+  if original_action.action().author() != &author {
+    return Ok(ValidateCallbackResult::Invalid("Only the author can edit a comment".to_string()));
+  }
+  let Some(original_eh) = original_action.action().entry_hash()
+    else { return Ok(ValidateCallbackResult::Invalid("Original action has no entry".to_string())) };
+  let Ok(original) = HereComment::try_from(must_get_entry(original_eh.clone())?.into_content())
+    else { return Ok(ValidateCallbackResult::Invalid("Original action is not a comment".to_string())) };
+  if original.here_link_ah != comment.here_link_ah || original.maybe_reply_to != comment.maybe_reply_to {
+    return Ok(ValidateCallbackResult::Invalid("A comment edit can only change its text".to_string()));
+  }
+  validate_comment(comment)
+}
+
+
+/// A comment link must go from the identity of the Here the comment is about to the comment
+fn validate_comment_link(base: AnyLinkableHash, target: AnyLinkableHash) -> ExternResult<ValidateCallbackResult> {
+  if !is_here_id(base.clone())? {
+    return Ok(ValidateCallbackResult::Invalid("Comment link must be from the session link of a Here's first version".to_string()));
+  }
+  let Some(comment_ah) = target.into_action_hash()
+    else { return Ok(ValidateCallbackResult::Invalid("Comment link must point to a comment action".to_string())) };
+  let action = must_get_action(comment_ah)?;
+  let Some(comment_eh) = action.action().entry_hash()
+    else { return Ok(ValidateCallbackResult::Invalid("Comment link must point to a comment action".to_string())) };
+  let Ok(comment) = HereComment::try_from(must_get_entry(comment_eh.clone())?.into_content())
+    else { return Ok(ValidateCallbackResult::Invalid("Comment link must point to a comment action".to_string())) };
+  if AnyLinkableHash::from(ActionHash::from(comment.here_link_ah)) != base {
+    return Ok(ValidateCallbackResult::Invalid("Comment is about another Here".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// Max size in bytes of a reaction emoji
+pub const MAX_REACTION_SIZE: usize = 32;
+
+
+/// A reaction is made by the agent it points to, with one reaction per emoji per agent on a Here.
+/// Earlier reactions are found on the author's own chain.
+fn validate_reaction_link(base: AnyLinkableHash, target: AnyLinkableHash, tag: LinkTag, action: CreateLink) -> ExternResult<ValidateCallbackResult> {
+  if target != AnyLinkableHash::from(action.author.clone()) {
+    return Ok(ValidateCallbackResult::Invalid("A reaction must point to its author".to_string()));
+  }
+  let emoji = tag.clone().into_inner();
+  if emoji.is_empty() || emoji.len() > MAX_REACTION_SIZE || String::from_utf8(emoji).is_err() {
+    return Ok(ValidateCallbackResult::Invalid("Reaction must be a short emoji".to_string()));
+  }
+  let scoped = ScopedLinkType::try_from(WhereLinkType::Reactions)?;
+  let activity = must_get_agent_activity(action.author.clone(), ChainFilter::new(action.prev_action.clone()))?;
+  let mut same_reactions: Vec<ActionHash> = Vec::new();
+  let mut deleted: Vec<ActionHash> = Vec::new();
+  for item in activity {
+    match item.action.action() {
+      Action::CreateLink(create) if create.zome_index == scoped.zome_index && create.link_type == scoped.zome_type
+        && create.base_address == base && create.tag == tag => {
+        same_reactions.push(item.action.as_hash().clone());
+      },
+      Action::DeleteLink(delete) => deleted.push(delete.link_add_address.clone()),
+      _ => {},
+    }
+  }
+  if same_reactions.iter().any(|ah| !deleted.contains(ah)) {
+    return Ok(ValidateCallbackResult::Invalid("Agent already reacted with this emoji".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// A session link must point to a Here of that session, with its summary or an empty tag.
+/// On a session in voting mode, an agent's live session links must stay within the vote budget.
+/// Links of other bases (spaces, anchors) are not restricted.
+fn validate_all_link(base: AnyLinkableHash, target: AnyLinkableHash, tag: LinkTag, action: CreateLink) -> ExternResult<ValidateCallbackResult> {
+  let Some(base_eh) = base.clone().into_entry_hash()
+    else { return Ok(ValidateCallbackResult::Valid) };
+  let Ok(session) = PlacementSession::try_from(must_get_entry(base_eh.clone())?.into_content())
+    else { return Ok(ValidateCallbackResult::Valid) };
+  let Some(here_eh) = target.into_entry_hash()
+    else { return Ok(ValidateCallbackResult::Invalid("Session link must point to a Here".to_string())) };
+  let Ok(here) = Here::try_from(must_get_entry(here_eh)?.into_content())
+    else { return Ok(ValidateCallbackResult::Invalid("Session link must point to a Here".to_string())) };
+  if EntryHash::from(here.session_eh.clone()) != base_eh {
+    return Ok(ValidateCallbackResult::Invalid("Here is of another session".to_string()));
+  }
+  if !tag.0.is_empty() && tag != here_link_tag(&here)? {
+    return Ok(ValidateCallbackResult::Invalid("Session link tag must be the summary of its Here".to_string()));
+  }
+  let Some(budget) = session.maybe_vote_budget
+    else { return Ok(ValidateCallbackResult::Valid) };
+  let scoped = ScopedLinkType::try_from(WhereLinkType::All)?;
+  let activity = must_get_agent_activity(action.author.clone(), ChainFilter::new(action.prev_action.clone()))?;
+  let mut votes: Vec<ActionHash> = Vec::new();
+  let mut deleted: Vec<ActionHash> = Vec::new();
+  for item in activity {
+    match item.action.action() {
+      Action::CreateLink(create) if create.zome_index == scoped.zome_index && create.link_type == scoped.zome_type
+        && create.base_address == base => {
+        votes.push(item.action.as_hash().clone());
+      },
+      Action::DeleteLink(delete) => deleted.push(delete.link_add_address.clone()),
+      _ => {},
+    }
+  }
+  let used = votes.iter().filter(|ah| !deleted.contains(ah)).count() as u32;
+  if used >= budget {
+    return Ok(ValidateCallbackResult::Invalid(format!("Vote budget of {} exhausted", budget)));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// A transform link must point to a transform of its session and be made by the session's creator
+fn validate_transform_link(base: AnyLinkableHash, target: AnyLinkableHash, action: CreateLink) -> ExternResult<ValidateCallbackResult> {
+  let Some(transform_eh) = target.into_entry_hash()
+    else { return Ok(ValidateCallbackResult::Invalid("Transform link must point to a SessionTransform".to_string())) };
+  let Ok(session_transform) = SessionTransform::try_from(must_get_entry(transform_eh)?.into_content())
+    else { return Ok(ValidateCallbackResult::Invalid("Transform link must point to a SessionTransform".to_string())) };
+  if AnyLinkableHash::from(EntryHash::from(session_transform.session_eh.clone())) != base {
+    return Ok(ValidateCallbackResult::Invalid("Transform is of another session".to_string()));
+  }
+  validate_session_creator(session_transform.session_eh, session_transform.session_ah, &action.author)
+}
+
+
+/// `session_ah` must be the creation of the session by `author`.
+/// Several agents can create the same session entry (e.g. scheduled sessions), any of them counts as its creator.
+fn validate_session_creator(session_eh: EntryHashB64, session_ah: ActionHashB64, author: &AgentPubKey) -> ExternResult<ValidateCallbackResult> {
+  let session_action = must_get_action(session_ah.into())?;
+  let Action::Create(create) = session_action.action()
+    else { return Ok(ValidateCallbackResult::Invalid("Session action must be a creation".to_string())) };
+  if create.entry_hash != EntryHash::from(session_eh) {
+    return Ok(ValidateCallbackResult::Invalid("Session action is the creation of another entry".to_string()));
+  }
+  if &create.author != author {
+    return Ok(ValidateCallbackResult::Invalid("Only the session's creator can do this".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// The session link and its Here, or None if the hash is not the session link of a Here
+fn must_get_here_link(hash: AnyLinkableHash) -> ExternResult<Option<(CreateLink, Here)>> {
+  let Some(link_ah) = hash.into_action_hash()
+    else { return Ok(None) };
+  let Action::CreateLink(create) = must_get_action(link_ah)?.action().clone()
+    else { return Ok(None) };
+  let scoped = ScopedLinkType::try_from(WhereLinkType::All)?;
+  if create.zome_index != scoped.zome_index || create.link_type != scoped.zome_type {
+    return Ok(None);
+  }
+  let Some(here_eh) = create.target_address.clone().into_entry_hash()
+    else { return Ok(None) };
+  let Ok(here) = Here::try_from(must_get_entry(here_eh)?.into_content())
+    else { return Ok(None) };
+  Ok(Some((create, here)))
+}
+
+
+/// True if the hash identifies a Here: the session link of its first version
+fn is_here_id(hash: AnyLinkableHash) -> ExternResult<bool> {
+  let maybe_here_link = must_get_here_link(hash)?;
+  Ok(maybe_here_link.map(|(_, here)| here.maybe_origin_ah.is_none()).unwrap_or(false))
+}
+
+
+/// An update link goes from the first version of a Here to a later version, by their author
+fn validate_here_update_link(base: AnyLinkableHash, target: AnyLinkableHash, action: CreateLink) -> ExternResult<ValidateCallbackResult> {
+  let Some((version_link, version)) = must_get_here_link(target)?
+    else { return Ok(ValidateCallbackResult::Invalid("Update link must point to the session link of a Here".to_string())) };
+  let Some(origin_ah) = version.maybe_origin_ah
+    else { return Ok(ValidateCallbackResult::Invalid("Update link must point to a later version of a Here".to_string())) };
+  if AnyLinkableHash::from(ActionHash::from(origin_ah)) != base {
+    return Ok(ValidateCallbackResult::Invalid("Update link must be from the first version of the Here".to_string()));
+  }
+  if version_link.author != action.author {
+    return Ok(ValidateCallbackResult::Invalid("Only the author of a Here can update it".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// An expiring link goes from an author to the session link of one of its Heres, tagged with the Here's expiry
+fn validate_expiring_link(base: AnyLinkableHash, target: AnyLinkableHash, tag: LinkTag, action: CreateLink) -> ExternResult<ValidateCallbackResult> {
+  if base != AnyLinkableHash::from(action.author.clone()) {
+    return Ok(ValidateCallbackResult::Invalid("Expiring link must be from its author".to_string()));
+  }
+  let Some((here_link, here)) = must_get_here_link(target)?
+    else { return Ok(ValidateCallbackResult::Invalid("Expiring link must point to the session link of a Here".to_string())) };
+  if here_link.author != action.author {
+    return Ok(ValidateCallbackResult::Invalid("Expiring link must point to a Here of its author".to_string()));
+  }
+  if here.maybe_expires_at.is_none() || here.maybe_expires_at != expiry_from_tag(&tag) {
+    return Ok(ValidateCallbackResult::Invalid("Expiring link must be tagged with the Here's expiry".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+/// Attachment links join the identity of a Here and the anchor of the HRL in their tag.
+/// The context, when set, must be JSON.
+fn validate_attachment_link(here: AnyLinkableHash, anchor: AnyLinkableHash, tag: LinkTag) -> ExternResult<ValidateCallbackResult> {
+  let Some(attachment) = HrlWithContext::from_tag(tag)
+    else { return Ok(ValidateCallbackResult::Invalid("Attachment link tag must be an HrlWithContext".to_string())) };
+  if let Some(context) = &attachment.context {
+    if serde_json::from_str::<serde_json::Value>(context).is_err() {
+      return Ok(ValidateCallbackResult::Invalid("Attachment context must be JSON".to_string()));
+    }
+  }
+  if AnyLinkableHash::from(hrl_anchor_hash(&attachment.hrl)?) != anchor {
+    return Ok(ValidateCallbackResult::Invalid("Attachment link must use the anchor of its HRL".to_string()));
+  }
+  if !is_here_id(here)? {
+    return Ok(ValidateCallbackResult::Invalid("Attachment must be on the session link of a Here's first version".to_string()));
+  }
+  Ok(ValidateCallbackResult::Valid)
+}
+
+
+#[cfg(test)]
+mod tests;
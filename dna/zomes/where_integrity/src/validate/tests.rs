@@ -0,0 +1,246 @@
+use std::collections::BTreeMap;
+use anchors::test_utils::*;
+
+use super::*;
+
+
+fn add_space(maybe_coordinate_system: Option<CoordinateSystem>) -> EntryHash {
+  add_test_entry(SpaceCoordinates {maybe_coordinate_system})
+}
+
+fn add_session(space_eh: EntryHash, maybe_opens_at: Option<Timestamp>, maybe_closes_at: Option<Timestamp>) -> EntryHash {
+  add_test_entry(PlacementSession {
+    name: "Monday".to_string(),
+    index: 0,
+    space_eh: space_eh.into(),
+    maybe_opens_at,
+    maybe_closes_at,
+    maybe_vote_budget: None,
+  })
+}
+
+fn new_here(session_eh: EntryHash, value: &str) -> Here {
+  Here {
+    value: value.to_string(),
+    session_eh: session_eh.into(),
+    meta: BTreeMap::new(),
+    maybe_expires_at: None,
+    maybe_origin_ah: None,
+  }
+}
+
+fn author() -> AgentPubKey {
+  agent(1)
+}
+
+fn is_valid(result: ExternResult<ValidateCallbackResult>) -> bool {
+  matches!(result.unwrap(), ValidateCallbackResult::Valid)
+}
+
+
+#[test]
+fn here_must_be_placed_while_its_session_is_open() {
+  TestHdi::install();
+  let space_eh = add_space(None);
+  let session_eh = add_session(space_eh, Some(Timestamp(100)), Some(Timestamp(200)));
+  let here = new_here(session_eh, "{\"x\": 1, \"y\": 2}");
+  assert!(is_valid(validate_here(here.clone(), Timestamp(150), author())));
+  assert!(!is_valid(validate_here(here.clone(), Timestamp(50), author())));
+  assert!(!is_valid(validate_here(here.clone(), Timestamp(250), author())));
+  let mut expired = here;
+  expired.maybe_expires_at = Some(Timestamp(150));
+  assert!(!is_valid(validate_here(expired, Timestamp(150), author())));
+}
+
+
+#[test]
+fn here_on_a_geo_space_must_be_a_position_in_range() {
+  TestHdi::install();
+  let session_eh = add_session(add_space(Some(CoordinateSystem::Geo)), None, None);
+  assert!(is_valid(validate_here(new_here(session_eh.clone(), "{\"lat\": 48.85, \"lon\": 2.35}"), Timestamp(0), author())));
+  assert!(!is_valid(validate_here(new_here(session_eh.clone(), "{\"lat\": 91, \"lon\": 2.35}"), Timestamp(0), author())));
+  assert!(!is_valid(validate_here(new_here(session_eh.clone(), "{\"lat\": 48.85, \"lon\": -181}"), Timestamp(0), author())));
+  assert!(!is_valid(validate_here(new_here(session_eh, "{\"x\": 1, \"y\": 2}"), Timestamp(0), author())));
+}
+
+
+#[test]
+fn here_must_be_in_a_session_of_a_space() {
+  TestHdi::install();
+  // A Here is not a PlacementSession
+  let session_eh = add_session(add_space(None), None, None);
+  let not_a_session = add_test_entry(new_here(session_eh, "{\"x\": 1, \"y\": 2}"));
+  assert!(!is_valid(validate_here(new_here(not_a_session, "{\"x\": 1, \"y\": 2}"), Timestamp(0), author())));
+}
+
+
+fn agent(byte: u8) -> AgentPubKey {
+  AgentPubKey::from_raw_32(vec![byte; 32])
+}
+
+/// Link of the given type, as created by `author`
+fn new_link(link_type: WhereLinkType, base: AnyLinkableHash, target: AnyLinkableHash, tag: LinkTag, author: AgentPubKey) -> CreateLink {
+  let scoped = ScopedLinkType::try_from(link_type).unwrap();
+  CreateLink {
+    author,
+    timestamp: Timestamp(0),
+    action_seq: 4,
+    prev_action: ActionHash::from_raw_32(vec![0; 32]),
+    base_address: base,
+    target_address: target,
+    zome_index: scoped.zome_index,
+    link_type: scoped.zome_type,
+    tag,
+    weight: Default::default(),
+  }
+}
+
+/// Store a Here and its session link, returns the link's ActionHash
+fn add_here_link(here: Here, author: AgentPubKey) -> AnyLinkableHash {
+  let tag = here_link_tag(&here).unwrap();
+  let session_eh = EntryHash::from(here.session_eh.clone());
+  let here_eh = add_test_entry(here);
+  add_test_action(Action::CreateLink(new_link(WhereLinkType::All, session_eh.into(), here_eh.into(), tag, author))).into()
+}
+
+
+#[test]
+fn expiring_link_goes_from_the_author_to_its_expiring_here() {
+  TestHdi::install();
+  let session_eh = add_session(add_space(None), None, None);
+  let mut here = new_here(session_eh.clone(), "{\"x\": 1, \"y\": 2}");
+  here.maybe_expires_at = Some(Timestamp(1000));
+  let link_ah = add_here_link(here, author());
+  let tag = expiry_tag(Timestamp(1000));
+  let link = |base: AgentPubKey, tag: LinkTag, by: AgentPubKey| {
+    validate_expiring_link(base.clone().into(), link_ah.clone(), tag.clone(), new_link(WhereLinkType::Expiring, base.into(), link_ah.clone(), tag, by))
+  };
+  assert!(is_valid(link(author(), tag.clone(), author())));
+  assert!(!is_valid(link(agent(2), tag.clone(), author())));
+  assert!(!is_valid(link(agent(2), tag.clone(), agent(2))));
+  assert!(!is_valid(link(author(), expiry_tag(Timestamp(2000)), author())));
+  assert!(!is_valid(link(author(), LinkTag::from(()), author())));
+  // The Here must expire
+  let lasting = add_here_link(new_here(session_eh, "{\"x\": 3, \"y\": 4}"), author());
+  let action = new_link(WhereLinkType::Expiring, author().into(), lasting.clone(), tag.clone(), author());
+  assert!(!is_valid(validate_expiring_link(author().into(), lasting, tag, action)));
+}
+
+
+/// Store the creation of an entry by `author`, returns its ActionHash
+fn add_creation(eh: EntryHash, author: AgentPubKey) -> ActionHash {
+  add_test_action(Action::Create(Create {
+    author,
+    timestamp: Timestamp(0),
+    action_seq: 4,
+    prev_action: ActionHash::from_raw_32(vec![0; 32]),
+    entry_type: EntryType::App(AppEntryDef::new(0.into(), 0.into(), EntryVisibility::Public)),
+    entry_hash: eh,
+    weight: Default::default(),
+  }))
+}
+
+
+#[test]
+fn snapshot_is_taken_by_the_session_creator_and_linked_from_its_session() {
+  TestHdi::install();
+  let space_eh = add_space(None);
+  let session_eh = add_session(space_eh.clone(), None, None);
+  let other_session_eh = add_session(space_eh, Some(Timestamp(1)), None);
+  let session_ah = add_creation(session_eh.clone(), author());
+  let snapshot = SessionSnapshot {session_eh: session_eh.clone().into(), session_ah: session_ah.into(), heres: Vec::new()};
+  let snapshot_eh: AnyLinkableHash = add_test_entry(snapshot.clone()).into();
+  let link = |base: EntryHash, by: AgentPubKey| {
+    validate_snapshot_link(base.clone().into(), snapshot_eh.clone(), new_link(WhereLinkType::Snapshot, base.into(), snapshot_eh.clone(), LinkTag::from(()), by))
+  };
+  assert!(is_valid(link(session_eh.clone(), author())));
+  assert!(!is_valid(link(session_eh.clone(), agent(2))));
+  assert!(!is_valid(link(other_session_eh, author())));
+  assert!(is_valid(validate_session_creator(snapshot.session_eh.clone(), snapshot.session_ah.clone(), &author())));
+  assert!(!is_valid(validate_session_creator(snapshot.session_eh, snapshot.session_ah, &agent(2))));
+  // The target must be a snapshot
+  let not_a_snapshot: AnyLinkableHash = session_eh.clone().into();
+  let action = new_link(WhereLinkType::Snapshot, session_eh.clone().into(), not_a_snapshot.clone(), LinkTag::from(()), author());
+  assert!(!is_valid(validate_snapshot_link(session_eh.into(), not_a_snapshot, action)));
+}
+
+
+#[test]
+fn reaction_is_one_short_emoji_per_agent_on_a_here() {
+  TestHdi::install();
+  let here_id: AnyLinkableHash = ActionHash::from_raw_32(vec![7; 32]).into();
+  let thumbs_up = LinkTag::new("👍".as_bytes().to_vec());
+  let react = |tag: LinkTag, target: AgentPubKey| {
+    let action = new_link(WhereLinkType::Reactions, here_id.clone(), target.clone().into(), tag.clone(), author());
+    validate_reaction_link(here_id.clone(), target.into(), tag, action)
+  };
+  assert!(is_valid(react(thumbs_up.clone(), author())));
+  assert!(!is_valid(react(thumbs_up.clone(), agent(2))));
+  assert!(!is_valid(react(LinkTag::new(Vec::new()), author())));
+  assert!(!is_valid(react(LinkTag::new(vec![0xff, 0xfe]), author())));
+  assert!(!is_valid(react(LinkTag::new("👍".repeat(9).into_bytes()), author())));
+  // An earlier live reaction with the same emoji on our chain
+  let mut earlier = new_link(WhereLinkType::Reactions, here_id.clone(), author().into(), thumbs_up.clone(), author());
+  earlier.action_seq = 2;
+  let earlier_ah = add_test_action(Action::CreateLink(earlier));
+  assert!(!is_valid(react(thumbs_up.clone(), author())));
+  assert!(is_valid(react(LinkTag::new("🎉".as_bytes().to_vec()), author())));
+  // Allowed again once it is deleted
+  add_test_action(Action::DeleteLink(DeleteLink {
+    author: author(),
+    timestamp: Timestamp(0),
+    action_seq: 3,
+    prev_action: ActionHash::from_raw_32(vec![0; 32]),
+    base_address: here_id.clone(),
+    link_add_address: earlier_ah,
+  }));
+  assert!(is_valid(react(thumbs_up, author())));
+}
+
+
+#[test]
+fn session_link_tag_is_the_summary_of_its_here_or_empty() {
+  TestHdi::install();
+  let session_eh = add_session(add_space(None), None, None);
+  let mut here = new_here(session_eh.clone(), "{\"x\": 1, \"y\": 2}");
+  here.meta.insert("tag".to_string(), "lunch".to_string());
+  let tag = here_link_tag(&here).unwrap();
+  assert_eq!(HereSummary::from_tag(tag.clone()), Some(HereSummary::of(&here)));
+  let other_tag = here_link_tag(&new_here(session_eh.clone(), "{\"x\": 3, \"y\": 4}")).unwrap();
+  let here_eh: AnyLinkableHash = add_test_entry(here).into();
+  let link = |tag: LinkTag| {
+    let action = new_link(WhereLinkType::All, session_eh.clone().into(), here_eh.clone(), tag.clone(), author());
+    validate_all_link(session_eh.clone().into(), here_eh.clone(), tag, action)
+  };
+  assert!(is_valid(link(tag)));
+  assert!(is_valid(link(LinkTag::from(()))));
+  assert!(!is_valid(link(other_tag)));
+  // Too large for a tag
+  let mut large = new_here(session_eh, "{\"x\": 1, \"y\": 2}");
+  large.meta.insert("name".to_string(), "x".repeat(MAX_SUMMARY_TAG_SIZE));
+  assert!(here_link_tag(&large).unwrap().0.is_empty());
+}
+
+
+#[test]
+fn attachment_links_join_a_here_id_and_the_anchor_of_their_hrl() {
+  TestHdi::install();
+  let session_eh = add_session(add_space(None), None, None);
+  let here_id = add_here_link(new_here(session_eh.clone(), "{\"x\": 1, \"y\": 2}"), author());
+  let hrl: Hrl = (DnaHash::from_raw_32(vec![3; 32]), EntryHash::from_raw_32(vec![4; 32]).into());
+  let anchor: AnyLinkableHash = hrl_anchor_hash(&hrl).unwrap().into();
+  let attach = |here: AnyLinkableHash, anchor: AnyLinkableHash, context: Option<&str>| {
+    let tag = HrlWithContext {hrl: hrl.clone(), context: context.map(|c| c.to_string())}.to_tag().unwrap();
+    validate_attachment_link(here, anchor, tag)
+  };
+  assert!(is_valid(attach(here_id.clone(), anchor.clone(), None)));
+  assert!(is_valid(attach(here_id.clone(), anchor.clone(), Some("{\"page\": 2}"))));
+  assert!(!is_valid(attach(here_id.clone(), anchor.clone(), Some("page 2"))));
+  assert!(!is_valid(attach(here_id.clone(), session_eh.clone().into(), None)));
+  assert!(!is_valid(validate_attachment_link(here_id.clone(), anchor.clone(), LinkTag::new("hrl".as_bytes().to_vec()))));
+  // Only on the first version of a Here
+  let mut version = new_here(session_eh, "{\"x\": 3, \"y\": 4}");
+  version.maybe_origin_ah = Some(ActionHash::try_from(here_id).unwrap().into());
+  let version_link = add_here_link(version, author());
+  assert!(!is_valid(attach(version_link, anchor, None)));
+}
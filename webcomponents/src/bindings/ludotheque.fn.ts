@@ -9,9 +9,17 @@ export const ludothequeFunctionNames: FunctionName[] = [
 	"get_zome_info", 
 	"get_dna_info",
 	"export_playset",
+	"search_playsets",
 	"create_playset",
 	"get_playset",
-	"get_all_playsets",];
+	"get_all_playsets",
+	"get_playsets_page",
+	"tag_playset",
+	"untag_playset",
+	"get_playsets_by_tag",
+	"get_playset_tags",
+	"get_all_playset_tags",
+];
 
 
 /** Generate tuple array of function names with given zomeName */
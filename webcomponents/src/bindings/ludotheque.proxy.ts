@@ -1,6 +1,6 @@
 /* This file is generated by zits. Do not edit manually */
 
-import {LudothequeEntry, LudothequeLinkType, ExportPlaysetInput, ImportPieceInput, Playset, PlaysetOutput, } from './ludotheque.types';
+import {DEFAULT_SHARD, MAX_SEARCH_RESULTS, NAME_INDEX_DEPTH, NAME_INDEX_ROOT_PATH, PLAYSETS_ROOT_PATH, TAGS_ROOT_PATH, LudothequeEntry, LudothequeLinkType, AnchorComponent, AnchorPath, ExportAssetInput, ExportPlaysetInput, GetPageInput, ImportPieceInput, PageCursor, PageOutput, Playset, PlaysetOutput, PlaysetSearchResult, TagPlaysetInput, } from './ludotheque.types';
 import {
 /** types.ts */
 HoloHash,
@@ -159,6 +159,10 @@ export class LudothequeProxy extends ZomeProxy {
     return this.call('export_playset', input);
   }
 
+  async searchPlaysets(query: string): Promise<PlaysetSearchResult[]> {
+    return this.call('search_playsets', query);
+  }
+
   async createPlayset(input: Playset): Promise<EntryHashB64> {
     return this.call('create_playset', input);
   }
@@ -170,4 +174,29 @@ export class LudothequeProxy extends ZomeProxy {
   async getAllPlaysets(): Promise<PlaysetOutput[]> {
     return this.call('get_all_playsets', null);
   }
+
+  async getPlaysetsPage(input: GetPageInput): Promise<PageOutput> {
+    return this.call('get_playsets_page', input);
+  }
+
+  async tagPlayset(input: TagPlaysetInput): Promise<void> {
+    return this.call('tag_playset', input);
+  }
+
+  async untagPlayset(input: TagPlaysetInput): Promise<void> {
+    return this.call('untag_playset', input);
+  }
+
+  async getPlaysetsByTag(tag: string): Promise<EntryHashB64[]> {
+    return this.call('get_playsets_by_tag', tag);
+  }
+
+  async getPlaysetTags(playsetEh: EntryHashB64): Promise<string[]> {
+    return this.call('get_playset_tags', playsetEh);
+  }
+
+  async getAllPlaysetTags(): Promise<string[]> {
+    return this.call('get_all_playset_tags', null);
+  }
+
 }
@@ -153,6 +153,23 @@ export interface ExportPlaysetInput {
 export interface ImportPieceInput {
   piece_type_name: string
   piece_entry: Entry
+  tags: string[]
+  assets: EntryHashB64[]
+}
+
+/** warning: duplicate struct in ludotheque zome */
+export interface ExportAssetInput {
+  cellId: CellId
+  assetEh: EntryHashB64
+}
+
+/** Maximum number of playsets returned by a search */
+export const MAX_SEARCH_RESULTS = 100;
+
+/**  */
+export interface PlaysetSearchResult {
+  hash: EntryHashB64
+  name: string
 }
 
 export interface PlaysetOutput {
@@ -160,6 +177,26 @@ export interface PlaysetOutput {
   content: Playset
 }
 
+/**  */
+export interface TagPlaysetInput {
+  playsetEh: EntryHashB64
+  tag: string
+}
+
+/**
+ * -------------------------------------------------------------------------------------------------
+ * Global consts
+ * -------------------------------------------------------------------------------------------------
+ * Root path of the playsets anchor
+ */
+export const PLAYSETS_ROOT_PATH = "playsets";
+
+/** Root path of the name-prefix index of playsets */
+export const NAME_INDEX_ROOT_PATH = "name_index";
+
+/** Root path of the tag anchors */
+export const TAGS_ROOT_PATH = "tags";
+
 export enum LudothequeEntryType {
 	Playset = 'Playset',
 }
@@ -174,9 +211,13 @@ export type LudothequeEntry =
  * List of all link kinds handled by this Zome
  */
 export type LudothequeLinkType =
-  | {All: null};
+  | {All: null} | {Playsets: null} | {NameIndex: null} | {Tagged: null} | {PlaysetTags: null};
 export enum LudothequeLinkTypeType {
 	All = 'All',
+	Playsets = 'Playsets',
+	NameIndex = 'NameIndex',
+	Tagged = 'Tagged',
+	PlaysetTags = 'PlaysetTags',
 }
 
 /** Playset Entry */
@@ -188,3 +229,33 @@ export interface Playset {
   emojiGroups: EntryHashB64[]
   spaces: EntryHashB64[]
 }
+
+/** Position of the next page: a shard key and the offset of its first link in that shard */
+export interface PageCursor {
+  shard: string
+  offset: number
+}
+
+/** Page request, starting from the first page when there is no cursor */
+export interface GetPageInput {
+  maybeCursor?: PageCursor
+  limit: number
+}
+
+/** Items of a page, and the cursor of the next page if there are more */
+export interface PageOutput<T> {
+  items: T[]
+  maybeNextCursor?: PageCursor
+}
+
+/** warning: mirror of hdk::hash_path::path::Component, must serialize the same way */
+export type AnchorComponent = number[];
+
+/** warning: mirror of hdk::hash_path::path::Path, must serialize the same way */
+export type AnchorPath = AnchorComponent[];
+
+/** Number of leading characters of a name indexed as path components */
+export const NAME_INDEX_DEPTH = 3;
+
+/** Shard used for names that do not start with an alphanumeric character */
+export const DEFAULT_SHARD = "_";
@@ -9,21 +9,49 @@ export const playsetFunctionNames: FunctionName[] = [
 	"get_zome_info", 
 	"get_dna_info",
 	"export_piece",
+	"export_asset",
 	"export_space",
 	"get_inventory",
 	"import_piece",
+	"search_pieces",
+	"create_asset",
+	"get_asset",
+	"get_all_assets",
+	"get_assets_page",
+	"attach_asset",
+	"get_surface_assets",
 	"create_emoji_group",
 	"get_emoji_group",
 	"get_all_emoji_groups",
+	"get_emoji_groups_page",
 	"create_space",
 	"get_space",
 	"get_spaces",
+	"get_spaces_page",
+	"get_spaces_using_template",
+	"get_spaces_using_marker",
+	"update_space",
+	"get_space_revisions",
+	"attach_sub_space",
+	"detach_sub_space",
+	"get_sub_spaces",
+	"get_parent_spaces",
+	"get_space_ancestors",
 	"create_svg_marker",
 	"get_svg_marker",
+	"render_svg_marker",
 	"get_svg_markers",
+	"get_svg_markers_page",
 	"create_template",
 	"get_template",
-	"get_templates",];
+	"get_templates",
+	"get_templates_page",
+	"tag_piece",
+	"untag_piece",
+	"get_pieces_by_tag",
+	"get_piece_tags",
+	"get_all_tags",
+];
 
 
 /** Generate tuple array of function names with given zomeName */
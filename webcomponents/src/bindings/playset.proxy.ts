@@ -1,6 +1,6 @@
 /* This file is generated by zits. Do not edit manually */
 
-import {MarkerPiece, PlaysetEntry, EmojiGroup, EmojiGroupOutput, ExportPieceInput, ExportSpaceInput, ExportSpaceOutput, GetInventoryOutput, ImportPieceInput, Space, SpaceOutput, SvgMarker, SvgMarkerOutput, Template, TemplateOutput, } from './playset.types';
+import {ASSETS_ROOT_PATH, ASSET_CHUNK_SIZE, COLOR_PLACEHOLDER, DEFAULT_SHARD, EMOJI_GROUPS_ROOT_PATH, INDEXED_PIECE_TYPES, INITIALS_PLACEHOLDER, MAX_SPACE_DEPTH, NAME_INDEX_DEPTH, NAME_INDEX_ROOT_PATH, SPACES_ROOT_PATH, SVG_MARKERS_ROOT_PATH, TAGS_ROOT_PATH, TEMPLATES_ROOT_PATH, CoordinateSystem, MarkerPiece, PlaysetEntry, SpaceAttachment, ZoneShape, AnchorComponent, AnchorPath, Asset, AssetChunk, AssetManifestOutput, AssetOutput, AttachAssetInput, AttachSubSpaceInput, CreateAssetInput, EmojiGroup, EmojiGroupOutput, ExportAssetInput, ExportPieceInput, ExportSpaceInput, ExportSpaceOutput, GetInventoryOutput, GetPageInput, ImportPieceInput, PageCursor, PageOutput, PieceSearchResult, RenderSvgMarkerInput, SearchPiecesInput, Space, SpaceOutput, SubSpaceOutput, SvgMarker, SvgMarkerOutput, TagPieceInput, Template, TemplateOutput, UpdateSpaceInput, Zone, } from './playset.types';
 import {
 /** types.ts */
 HoloHash,
@@ -159,6 +159,10 @@ export class PlaysetProxy extends ZomeProxy {
     return this.call('export_piece', input);
   }
 
+  async exportAsset(input: ExportAssetInput): Promise<void> {
+    return this.call('export_asset', input);
+  }
+
   async exportSpace(input: ExportSpaceInput): Promise<ExportSpaceOutput> {
     return this.call('export_space', input);
   }
@@ -171,6 +175,34 @@ export class PlaysetProxy extends ZomeProxy {
     return this.call('import_piece', input);
   }
 
+  async searchPieces(input: SearchPiecesInput): Promise<PieceSearchResult[]> {
+    return this.call('search_pieces', input);
+  }
+
+  async createAsset(input: CreateAssetInput): Promise<EntryHashB64> {
+    return this.call('create_asset', input);
+  }
+
+  async getAsset(assetEh: EntryHashB64): Promise<AssetOutput | null> {
+    return this.call('get_asset', assetEh);
+  }
+
+  async getAllAssets(): Promise<AssetManifestOutput[]> {
+    return this.call('get_all_assets', null);
+  }
+
+  async getAssetsPage(input: GetPageInput): Promise<PageOutput> {
+    return this.call('get_assets_page', input);
+  }
+
+  async attachAsset(input: AttachAssetInput): Promise<void> {
+    return this.call('attach_asset', input);
+  }
+
+  async getSurfaceAssets(pieceEh: EntryHashB64): Promise<EntryHashB64[]> {
+    return this.call('get_surface_assets', pieceEh);
+  }
+
   async createEmojiGroup(input: EmojiGroup): Promise<EntryHashB64> {
     return this.call('create_emoji_group', input);
   }
@@ -183,6 +215,10 @@ export class PlaysetProxy extends ZomeProxy {
     return this.call('get_all_emoji_groups', null);
   }
 
+  async getEmojiGroupsPage(input: GetPageInput): Promise<PageOutput> {
+    return this.call('get_emoji_groups_page', input);
+  }
+
   async createSpace(input: Space): Promise<EntryHashB64> {
     return this.call('create_space', input);
   }
@@ -195,6 +231,46 @@ export class PlaysetProxy extends ZomeProxy {
     return this.call('get_spaces', null);
   }
 
+  async getSpacesPage(input: GetPageInput): Promise<PageOutput> {
+    return this.call('get_spaces_page', input);
+  }
+
+  async getSpacesUsingTemplate(templateEh: EntryHashB64): Promise<EntryHashB64[]> {
+    return this.call('get_spaces_using_template', templateEh);
+  }
+
+  async getSpacesUsingMarker(markerEh: EntryHashB64): Promise<EntryHashB64[]> {
+    return this.call('get_spaces_using_marker', markerEh);
+  }
+
+  async updateSpace(input: UpdateSpaceInput): Promise<EntryHashB64> {
+    return this.call('update_space', input);
+  }
+
+  async getSpaceRevisions(spaceEh: EntryHashB64): Promise<EntryHashB64[]> {
+    return this.call('get_space_revisions', spaceEh);
+  }
+
+  async attachSubSpace(input: AttachSubSpaceInput): Promise<void> {
+    return this.call('attach_sub_space', input);
+  }
+
+  async detachSubSpace(input: AttachSubSpaceInput): Promise<void> {
+    return this.call('detach_sub_space', input);
+  }
+
+  async getSubSpaces(spaceEh: EntryHashB64): Promise<SubSpaceOutput[]> {
+    return this.call('get_sub_spaces', spaceEh);
+  }
+
+  async getParentSpaces(spaceEh: EntryHashB64): Promise<SubSpaceOutput[]> {
+    return this.call('get_parent_spaces', spaceEh);
+  }
+
+  async getSpaceAncestors(spaceEh: EntryHashB64): Promise<EntryHashB64[]> {
+    return this.call('get_space_ancestors', spaceEh);
+  }
+
   async createSvgMarker(input: SvgMarker): Promise<EntryHashB64> {
     return this.call('create_svg_marker', input);
   }
@@ -203,10 +279,18 @@ export class PlaysetProxy extends ZomeProxy {
     return this.call('get_svg_marker', input);
   }
 
+  async renderSvgMarker(input: RenderSvgMarkerInput): Promise<string> {
+    return this.call('render_svg_marker', input);
+  }
+
   async getSvgMarkers(): Promise<SvgMarkerOutput[]> {
     return this.call('get_svg_markers', null);
   }
 
+  async getSvgMarkersPage(input: GetPageInput): Promise<PageOutput> {
+    return this.call('get_svg_markers_page', input);
+  }
+
   async createTemplate(input: Template): Promise<EntryHashB64> {
     return this.call('create_template', input);
   }
@@ -218,4 +302,29 @@ export class PlaysetProxy extends ZomeProxy {
   async getTemplates(): Promise<TemplateOutput[]> {
     return this.call('get_templates', null);
   }
+
+  async getTemplatesPage(input: GetPageInput): Promise<PageOutput> {
+    return this.call('get_templates_page', input);
+  }
+
+  async tagPiece(input: TagPieceInput): Promise<void> {
+    return this.call('tag_piece', input);
+  }
+
+  async untagPiece(input: TagPieceInput): Promise<void> {
+    return this.call('untag_piece', input);
+  }
+
+  async getPiecesByTag(tag: string): Promise<EntryHashB64[]> {
+    return this.call('get_pieces_by_tag', tag);
+  }
+
+  async getPieceTags(pieceEh: EntryHashB64): Promise<string[]> {
+    return this.call('get_piece_tags', pieceEh);
+  }
+
+  async getAllTags(): Promise<string[]> {
+    return this.call('get_all_tags', null);
+  }
+
 }
@@ -150,6 +150,11 @@ export interface ExportPieceInput {
   pieceTypeName: string
 }
 
+export interface ExportAssetInput {
+  cellId: CellId
+  assetEh: EntryHashB64
+}
+
 export interface ExportSpaceInput {
   cellId: CellId
   spaceEh: EntryHashB64
@@ -171,6 +176,43 @@ export interface GetInventoryOutput {
 export interface ImportPieceInput {
   piece_type_name: string
   piece_entry: Entry
+  tags: string[]
+  assets: EntryHashB64[]
+}
+
+/**  */
+export interface SearchPiecesInput {
+  query: string
+  kinds: string[]
+  limit: number
+}
+
+/**  */
+export interface PieceSearchResult {
+  pieceTypeName: string
+  hash: EntryHashB64
+  name: string
+}
+
+export interface CreateAssetInput {
+  name: string
+  mimeType: string
+  data: number[]
+}
+
+export interface AssetOutput {
+  manifest: Asset
+  data: number[]
+}
+
+export interface AssetManifestOutput {
+  hash: EntryHashB64
+  manifest: Asset
+}
+
+export interface AttachAssetInput {
+  pieceEh: EntryHashB64
+  assetEh: EntryHashB64
 }
 
 export interface EmojiGroupOutput {
@@ -184,28 +226,113 @@ export interface SpaceOutput {
   content: Space
 }
 
+/**  */
+export interface UpdateSpaceInput {
+  spaceEh: EntryHashB64
+  newSpace: Space
+}
+
+/** Max number of ancestors returned, guarding against cycles */
+export const MAX_SPACE_DEPTH = 16;
+
+/**  */
+export interface AttachSubSpaceInput {
+  parentEh: EntryHashB64
+  childEh: EntryHashB64
+  attachment: SpaceAttachment
+}
+
+/**  */
+export interface SubSpaceOutput {
+  spaceEh: EntryHashB64
+  attachment: SpaceAttachment
+}
+
 export interface SvgMarkerOutput {
   hash: EntryHashB64
   content: SvgMarker
 }
 
+/** Placeholders substituted in the value of an SvgMarker by `render_svg_marker()` */
+export const COLOR_PLACEHOLDER = "%%color%%";
+
+export const INITIALS_PLACEHOLDER = "%%initials%%";
+
+export interface RenderSvgMarkerInput {
+  svgMarkerEh: EntryHashB64
+  color: string
+  initials: string
+}
+
 export interface TemplateOutput {
   hash: EntryHashB64
   content: Template
 }
 
+/**  */
+export interface TagPieceInput {
+  pieceEh: EntryHashB64
+  tag: string
+}
+
+/**
+ * -------------------------------------------------------------------------------------------------
+ * Global consts
+ * -------------------------------------------------------------------------------------------------
+ * Root path of each piece kind's anchor
+ */
+export const TEMPLATES_ROOT_PATH = "templates";
+
+export const SVG_MARKERS_ROOT_PATH = "svg-marker";
+
+export const EMOJI_GROUPS_ROOT_PATH = "emoji-groups";
+
+export const SPACES_ROOT_PATH = "spaces";
+
+export const ASSETS_ROOT_PATH = "assets";
+
+/** Root path of the name-prefix index of pieces */
+export const NAME_INDEX_ROOT_PATH = "name_index";
+
+/** Root path of the tag anchors */
+export const TAGS_ROOT_PATH = "tags";
+
 export enum PlaysetEntryType {
 	SvgMarker = 'SvgMarker',
 	EmojiGroup = 'EmojiGroup',
 	Template = 'Template',
 	Space = 'Space',
+	Asset = 'Asset',
+	AssetChunk = 'AssetChunk',
 }
 export type PlaysetEntryVariantSvgMarker = {SvgMarker: SvgMarker}
 export type PlaysetEntryVariantEmojiGroup = {EmojiGroup: EmojiGroup}
 export type PlaysetEntryVariantTemplate = {Template: Template}
 export type PlaysetEntryVariantSpace = {Space: Space}
+export type PlaysetEntryVariantAsset = {Asset: Asset}
+export type PlaysetEntryVariantAssetChunk = {AssetChunk: AssetChunk}
 export type PlaysetEntry = 
- | PlaysetEntryVariantSvgMarker | PlaysetEntryVariantEmojiGroup | PlaysetEntryVariantTemplate | PlaysetEntryVariantSpace;
+ | PlaysetEntryVariantSvgMarker | PlaysetEntryVariantEmojiGroup | PlaysetEntryVariantTemplate | PlaysetEntryVariantSpace | PlaysetEntryVariantAsset | PlaysetEntryVariantAssetChunk;
+
+/** Maximum size in bytes of the data held by one AssetChunk */
+export const ASSET_CHUNK_SIZE = 256 * 1024;
+
+/**
+ * Asset Entry: manifest of binary data split into AssetChunk entries.
+ * Templates and spaces reference the assets of their surface with SurfaceAssets links, not by hash in the surface.
+ */
+export interface Asset {
+  name: string
+  mimeType: string
+  size: number
+  hash: string
+  chunks: EntryHashB64[]
+}
+
+/** AssetChunk Entry */
+export interface AssetChunk {
+  data: number[]
+}
 
 /** EmojiGroup Entry */
 export interface EmojiGroup {
@@ -230,6 +357,8 @@ export interface Space {
   surface: string
   maybeMarkerPiece?: MarkerPiece
   meta: Record<string, string>
+  zones: Zone[]
+  maybeCoordinateSystem?: CoordinateSystem
 }
 
 /** SvgMarker Entry */
@@ -243,3 +372,65 @@ export interface Template {
   name: string
   surface: string
 }
+
+/** Position of the next page: a shard key and the offset of its first link in that shard */
+export interface PageCursor {
+  shard: string
+  offset: number
+}
+
+/** Page request, starting from the first page when there is no cursor */
+export interface GetPageInput {
+  maybeCursor?: PageCursor
+  limit: number
+}
+
+/** Items of a page, and the cursor of the next page if there are more */
+export interface PageOutput<T> {
+  items: T[]
+  maybeNextCursor?: PageCursor
+}
+
+/** warning: mirror of hdk::hash_path::path::Component, must serialize the same way */
+export type AnchorComponent = number[];
+
+/** warning: mirror of hdk::hash_path::path::Path, must serialize the same way */
+export type AnchorPath = AnchorComponent[];
+
+/** Number of leading characters of a name indexed as path components */
+export const NAME_INDEX_DEPTH = 3;
+
+/** Shard used for names that do not start with an alphanumeric character */
+export const DEFAULT_SHARD = "_";
+
+/**
+ * Where a sub-space is attached on the surface of its parent space.
+ * Carried in the tag of the links between the two spaces.
+ */
+export enum SpaceAttachmentType {
+	Whole = 'Whole',
+	Zone = 'Zone',
+	Point = 'Point',
+}
+export type SpaceAttachment = unknown
+
+/** How the value of a Here placed on a Space is interpreted */
+export type CoordinateSystem =
+  | {cartesian: null} | {geo: null};
+export enum CoordinateSystemType {
+	Cartesian = 'Cartesian',
+	Geo = 'Geo',
+}
+
+/** Named region of a Space's surface */
+export interface Zone {
+  name: string
+  shape: ZoneShape
+}
+
+/** Zone outline in surface coordinates */
+export enum ZoneShapeType {
+	Rect = 'Rect',
+	Polygon = 'Polygon',
+}
+export type ZoneShape = unknown
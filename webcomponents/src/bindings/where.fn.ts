@@ -8,21 +8,61 @@ export const whereFunctionNames: FunctionName[] = [
 	"entry_defs", 
 	"get_zome_info", 
 	"get_dna_info",
+	"add_here_attachment",
+	"remove_here_attachment",
+	"get_here_attachments",
+	"get_heres_attached_to",
+	"get_carried_heres",
+	"confirm_carried_here",
+	"discard_carried_here",
+	"add_here_comment",
+	"edit_here_comment",
+	"delete_here_comment",
+	"get_here_comments",
+	"get_session_diff",
+	"delete_expired_heres",
+	"get_heres_within",
+	"export_session_geojson",
 	"add_here",
 	"update_here",
 	"delete_here",
 	"get_heres",
+	"get_heres_with_options",
+	"get_heres_since",
+	"get_heres_in_box",
+	"get_nearest_heres",
 	"hide_space",
 	"unhide_space",
 	"get_hidden_spaces",
 
+	"get_agent_color",
+	"render_agent_marker",
+	"migrate_heres",
+	"get_session_transform",
 	"get_session",
 	"get_session_from_eh",
 	"get_space_sessions",
 	"create_sessions",
 	"create_next_session",
+	"get_author_profile",
+	"react_to_here",
+	"unreact_to_here",
+	"get_here_reactions",
+	"create_session_schedule",
+	"get_space_schedules",
+	"delete_session_schedule",
+	"create_scheduled_sessions",
 
-	"notify_peers",];
+	"notify_peers",
+	"snapshot_session",
+	"get_snapshot",
+	"get_sub_space_rollup",
+	"get_session_stats",
+	"compare_sessions",
+	"get_remaining_votes",
+	"get_vote_tally",
+	"get_session_zones",
+];
 
 
 /** Generate tuple array of function names with given zomeName */
@@ -1,6 +1,6 @@
 /* This file is generated by zits. Do not edit manually */
 
-import {PLAYSET_DEFAULT_COORDINATOR_ZOME_NAME, WHERE_DEFAULT_COORDINATOR_ZOME_NAME, WHERE_DEFAULT_INTEGRITY_ZOME_NAME, WHERE_DEFAULT_ROLE_NAME, Message, WhereEntry, WhereLinkType, AddHereInput, CreateNextSessionInput, GetSessionInput, Here, HereOutput, NotifyInput, PlacementSession, SignalPayload, SpaceSessionsInput, UpdateHereInput, } from './where.types';
+import {ATTACHABLES_ROOT_PATH, AUTHOR_META_KEYS, CARTESIAN_GRID, EARTH_RADIUS_KM, EMOJI_META_KEY, EXPIRED_HERES_CRON, GEO_GRID, HERE_INDEX_ROOT_PATH, MAX_NEAREST_RINGS, MAX_QUERIED_CELLS, MAX_REACTION_SIZE, MAX_SUMMARY_TAG_SIZE, MIN_HUE_GAP, PLAYSET_DEFAULT_COORDINATOR_ZOME_NAME, PROFILES_DEFAULT_COORDINATOR_ZOME_NAME, SCHEDULED_SESSIONS_CRON, TAG_META_KEY, WHERE_DEFAULT_COORDINATOR_ZOME_NAME, WHERE_DEFAULT_INTEGRITY_ZOME_NAME, WHERE_DEFAULT_ROLE_NAME, CoordinateSystem, Message, SchedulePeriod, SpaceAttachment, WhereEntry, WhereLinkType, ZoneShape, AddHereCommentInput, AddHereInput, AgentMove, AuthorProfile, CarriedHereOutput, CommentOutput, CompareSessionsInput, Coord, CreateNextSessionInput, CreateSessionScheduleInput, EditHereCommentInput, GeoCoord, GetAgentColorInput, GetHeresInBoxInput, GetHeresInput, GetHeresSinceInput, GetHeresWithinInput, GetNearestHeresInput, GetSessionInput, Here, HereAttachmentInput, HereComment, HereOutput, HereSummary, HereTransform, HeresSinceOutput, HrlWithContext, MigrateHeresInput, NotifyInput, PlacementSession, ProfileInfo, ReactInput, ReactionOutput, RenderAgentMarkerInput, RenderSvgMarkerInput, SessionDiff, SessionDiffInput, SessionSchedule, SessionSnapshot, SessionStats, SessionStatsComparison, SessionTransform, SessionZonesOutput, SignalPayload, SnapshotHere, SpaceCoordinates, SpaceInfo, SpaceSessionsInput, SubSpaceOutput, SubSpaceRollup, TallyEntry, UpdateHereInput, VoteTally, Zone, ZoneOccupants, } from './where.types';
 import {
 /** types.ts */
 HoloHash,
@@ -155,6 +155,66 @@ export class WhereProxy extends ZomeProxy {
   static readonly DEFAULT_ZOME_NAME = "zWhere"
   static readonly FN_NAMES = whereFunctionNames
  
+  async addHereAttachment(input: HereAttachmentInput): Promise<void> {
+    return this.call('add_here_attachment', input);
+  }
+
+  async removeHereAttachment(input: HereAttachmentInput): Promise<void> {
+    return this.call('remove_here_attachment', input);
+  }
+
+  async getHereAttachments(hereLinkAh: ActionHashB64): Promise<HrlWithContext[]> {
+    return this.call('get_here_attachments', hereLinkAh);
+  }
+
+  async getHeresAttachedTo(hrl: Hrl): Promise<HereOutput[]> {
+    return this.call('get_heres_attached_to', hrl);
+  }
+
+  async getCarriedHeres(sessionEh: EntryHashB64): Promise<CarriedHereOutput[]> {
+    return this.call('get_carried_heres', sessionEh);
+  }
+
+  async confirmCarriedHere(carriedAh: ActionHashB64): Promise<ActionHashB64> {
+    return this.call('confirm_carried_here', carriedAh);
+  }
+
+  async discardCarriedHere(carriedAh: ActionHashB64): Promise<void> {
+    return this.call('discard_carried_here', carriedAh);
+  }
+
+  async addHereComment(input: AddHereCommentInput): Promise<CommentOutput> {
+    return this.call('add_here_comment', input);
+  }
+
+  async editHereComment(input: EditHereCommentInput): Promise<CommentOutput> {
+    return this.call('edit_here_comment', input);
+  }
+
+  async deleteHereComment(commentAh: ActionHashB64): Promise<void> {
+    return this.call('delete_here_comment', commentAh);
+  }
+
+  async getHereComments(hereLinkAh: ActionHashB64): Promise<CommentOutput[]> {
+    return this.call('get_here_comments', hereLinkAh);
+  }
+
+  async getSessionDiff(input: SessionDiffInput): Promise<SessionDiff> {
+    return this.call('get_session_diff', input);
+  }
+
+  async deleteExpiredHeres(): Schedule | null {
+    return this.call('delete_expired_heres', null);
+  }
+
+  async getHeresWithin(input: GetHeresWithinInput): Promise<HereOutput[]> {
+    return this.call('get_heres_within', input);
+  }
+
+  async exportSessionGeojson(sessionEh: EntryHashB64): Promise<string> {
+    return this.call('export_session_geojson', sessionEh);
+  }
+
   async addHere(input: AddHereInput): Promise<ActionHashB64> {
     return this.call('add_here', input);
   }
@@ -171,6 +231,22 @@ export class WhereProxy extends ZomeProxy {
     return this.call('get_heres', sessionEh);
   }
 
+  async getHeresWithOptions(input: GetHeresInput): Promise<HereOutput[]> {
+    return this.call('get_heres_with_options', input);
+  }
+
+  async getHeresSince(input: GetHeresSinceInput): Promise<HeresSinceOutput> {
+    return this.call('get_heres_since', input);
+  }
+
+  async getHeresInBox(input: GetHeresInBoxInput): Promise<HereOutput[]> {
+    return this.call('get_heres_in_box', input);
+  }
+
+  async getNearestHeres(input: GetNearestHeresInput): Promise<HereOutput[]> {
+    return this.call('get_nearest_heres', input);
+  }
+
   async hideSpace(spaceEh64: EntryHashB64): Promise<ActionHash> {
     return this.call('hide_space', spaceEh64);
   }
@@ -184,6 +260,22 @@ export class WhereProxy extends ZomeProxy {
   }
 
 
+  async getAgentColor(input: GetAgentColorInput): Promise<string> {
+    return this.call('get_agent_color', input);
+  }
+
+  async renderAgentMarker(input: RenderAgentMarkerInput): Promise<string> {
+    return this.call('render_agent_marker', input);
+  }
+
+  async migrateHeres(input: MigrateHeresInput): Promise<EntryHashB64> {
+    return this.call('migrate_heres', input);
+  }
+
+  async getSessionTransform(sessionEh: EntryHashB64): Promise<HereTransform | null> {
+    return this.call('get_session_transform', sessionEh);
+  }
+
   async getSession(input: GetSessionInput): Promise<EntryHashB64 | null> {
     return this.call('get_session', input);
   }
@@ -204,8 +296,73 @@ export class WhereProxy extends ZomeProxy {
     return this.call('create_next_session', input);
   }
 
+  async getAuthorProfile(agent: AgentPubKeyB64): Promise<AuthorProfile | null> {
+    return this.call('get_author_profile', agent);
+  }
+
+  async reactToHere(input: ReactInput): Promise<void> {
+    return this.call('react_to_here', input);
+  }
+
+  async unreactToHere(input: ReactInput): Promise<void> {
+    return this.call('unreact_to_here', input);
+  }
+
+  async getHereReactions(hereLinkAh: ActionHashB64): Promise<ReactionOutput[]> {
+    return this.call('get_here_reactions', hereLinkAh);
+  }
+
+  async createSessionSchedule(input: CreateSessionScheduleInput): Promise<EntryHashB64> {
+    return this.call('create_session_schedule', input);
+  }
+
+  async getSpaceSchedules(spaceEh: EntryHashB64): Promise<[EntryHashB64, SessionSchedule][]> {
+    return this.call('get_space_schedules', spaceEh);
+  }
+
+  async deleteSessionSchedule(scheduleEh: EntryHashB64): Promise<void> {
+    return this.call('delete_session_schedule', scheduleEh);
+  }
+
+  async createScheduledSessions(): Schedule | null {
+    return this.call('create_scheduled_sessions', null);
+  }
+
 
   async notifyPeers(input: NotifyInput): Promise<void> {
     return this.call('notify_peers', input);
   }
+
+  async snapshotSession(sessionEh: EntryHashB64): Promise<EntryHashB64> {
+    return this.call('snapshot_session', sessionEh);
+  }
+
+  async getSnapshot(sessionEh: EntryHashB64): Promise<SessionSnapshot | null> {
+    return this.call('get_snapshot', sessionEh);
+  }
+
+  async getSubSpaceRollup(spaceEh: EntryHashB64): Promise<SubSpaceRollup[]> {
+    return this.call('get_sub_space_rollup', spaceEh);
+  }
+
+  async getSessionStats(sessionEh: EntryHashB64): Promise<SessionStats> {
+    return this.call('get_session_stats', sessionEh);
+  }
+
+  async compareSessions(input: CompareSessionsInput): Promise<SessionStatsComparison> {
+    return this.call('compare_sessions', input);
+  }
+
+  async getRemainingVotes(sessionEh: EntryHashB64): Promise<number | null> {
+    return this.call('get_remaining_votes', sessionEh);
+  }
+
+  async getVoteTally(sessionEh: EntryHashB64): Promise<VoteTally> {
+    return this.call('get_vote_tally', sessionEh);
+  }
+
+  async getSessionZones(sessionEh: EntryHashB64): Promise<SessionZonesOutput> {
+    return this.call('get_session_zones', sessionEh);
+  }
+
 }
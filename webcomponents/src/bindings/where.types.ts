@@ -144,11 +144,97 @@ ValidationStatus,
 ValidationReceipt,
    } from '@holochain-open-dev/core-types';
 
+/**  */
+export interface HereAttachmentInput {
+  /** Session link of any version of the Here */
+  hereLinkAh: ActionHashB64
+  hrlWithContext: HrlWithContext
+}
+
+/** A Here of a previous session offered to its author in a new session */
+export interface CarriedHereOutput {
+  carriedAh: ActionHashB64
+  author: AgentPubKeyB64
+  here: Here
+}
+
+/**  */
+export interface AddHereCommentInput {
+  /** Session link of any version of the Here */
+  hereLinkAh: ActionHashB64
+  text: string
+  maybeReplyTo?: ActionHashB64
+}
+
+/**  */
+export interface EditHereCommentInput {
+  commentAh: ActionHashB64
+  text: string
+}
+
+/** Latest version of a comment */
+export interface CommentOutput {
+  /** ActionHash of the comment's creation, its identity across edits */
+  commentAh: ActionHashB64
+  comment: HereComment
+  author: AgentPubKeyB64
+  createdAt: Timestamp
+  maybeEditedAt?: Timestamp
+}
+
+/**  */
+export interface SessionDiffInput {
+  fromSessionEh: EntryHashB64
+  toSessionEh: EntryHashB64
+}
+
+/** Latest Heres of an agent present in both sessions */
+export interface AgentMove {
+  agent: AgentPubKeyB64
+  from: HereOutput
+  to: HereOutput
+  /** Distance between the two positions, in km on geo spaces */
+  maybeDisplacement?: number
+  tagChanged: boolean
+  emojiChanged: boolean
+}
+
+/** Per-agent changes between two sessions of a space */
+export interface SessionDiff {
+  moves: AgentMove[]
+  /** Latest Heres of agents only in the second session */
+  joined: HereOutput[]
+  /** Latest Heres of agents only in the first session */
+  left: HereOutput[]
+}
+
+/** How often online agents delete their expired Heres */
+export const EXPIRED_HERES_CRON = "0 */15 * * * * *";
+
+/**  */
+export interface GetHeresWithinInput {
+  sessionEh: EntryHashB64
+  center: GeoCoord
+  radiusKm: number
+}
+
+/** Mean radius of the Earth in km */
+export const EARTH_RADIUS_KM = 6371.0;
+
+/** Position on a 2D surface, the format of `Here.value` on cartesian spaces */
+export interface Coord {
+  x: number
+  y: number
+}
+
 export interface AddHereInput {
   spaceEh: EntryHashB64
   sessionIndex: number
   value: string
+  /** Identity fields (see `AUTHOR_META_KEYS`) are kept as sent, `with_profiles` in GetHeresInput reads the profiles zome instead */
   meta: Record<string, string>
+  /** Time-to-live of the Here, in seconds */
+  maybeTtlSecs?: number
 }
 
 /** Input to update a Here */
@@ -162,9 +248,94 @@ export interface HereOutput {
   entry: Here
   linkAh: ActionHashB64
   author: AgentPubKeyB64
+  /** Number of reactions per emoji, when requested */
+  maybeReactions?: Record<string, string>
+  /** Current profile of the author, when requested */
+  maybeProfile?: AuthorProfile
+}
+
+/**  */
+export interface GetHeresInput {
+  sessionEh: EntryHashB64
+  /** Fetch every Here entry instead of using the link summaries */
+  fullEntries: boolean
+  /** Include the reaction counts of each Here */
+  withReactions: boolean
+  /** Include the current profile of each Here's author */
+  withProfiles: boolean
+}
+
+/**  */
+export interface GetHeresSinceInput {
+  sessionEh: EntryHashB64
+  since: Timestamp
+}
+
+/** Changes to the Heres of a session after a point in time */
+export interface HeresSinceOutput {
+  created: HereOutput[]
+  /** Session links deleted, i.e. `link_ah` of removed Heres */
+  deleted: ActionHashB64[]
+  /** Value of `since` for the next call */
+  cursor: Timestamp
+}
+
+/** Root path of the spatial index of Heres */
+export const HERE_INDEX_ROOT_PATH = "here_index";
+
+/** Above this number of coarse cells, a box query scans the whole session instead */
+export const MAX_QUERIED_CELLS = 64;
+
+/** Number of rings of coarse cells searched around the center by nearest queries */
+export const MAX_NEAREST_RINGS = 4;
+
+/**  */
+export interface GetHeresInBoxInput {
+  sessionEh: EntryHashB64
+  min: Coord
+  max: Coord
+}
+
+/**  */
+export interface GetNearestHeresInput {
+  sessionEh: EntryHashB64
+  center: Coord
+  count: number
 }
 
-export const PLAYSET_DEFAULT_COORDINATOR_ZOME_NAME = "where_playset";
+export const PLAYSET_DEFAULT_COORDINATOR_ZOME_NAME = "zPlayset";
+
+export const PROFILES_DEFAULT_COORDINATOR_ZOME_NAME = "profiles";
+
+/** Min hue difference, in degrees, between two agents of a space */
+export const MIN_HUE_GAP = 24.0;
+
+/** warning: duplicate of playset::RenderSvgMarkerInput */
+export interface RenderSvgMarkerInput {
+  svgMarkerEh: EntryHashB64
+  color: string
+  initials: string
+}
+
+/**  */
+export interface GetAgentColorInput {
+  agent: AgentPubKeyB64
+  /** Avoid colors close to the ones of the other agents placed on this space */
+  maybeSpaceEh?: EntryHashB64
+}
+
+/**  */
+export interface RenderAgentMarkerInput {
+  svgMarkerEh: EntryHashB64
+  agent: AgentPubKeyB64
+  maybeSpaceEh?: EntryHashB64
+}
+
+/**  */
+export interface MigrateHeresInput {
+  sessionEh: EntryHashB64
+  transform: HereTransform
+}
 
 export interface GetSessionInput {
   spaceEh: EntryHashB64
@@ -179,6 +350,51 @@ export interface SpaceSessionsInput {
 export interface CreateNextSessionInput {
   name: string
   spaceEh: EntryHashB64
+  /** Offer each agent's latest Here of the previous session as a placement to confirm or discard */
+  carryForward: boolean
+  maybeOpensAt?: Timestamp
+  maybeClosesAt?: Timestamp
+  /** Voting mode: max number of Heres per agent */
+  maybeVoteBudget?: number
+}
+
+/** warning: partial duplicate of hc_zome_profiles_integrity::Profile */
+export interface ProfileInfo {
+  nickname: string
+  fields: Record<string, string>
+}
+
+/** Identity of a Here's author for display */
+export interface AuthorProfile {
+  nickname: string
+  maybeAvatar?: string
+  maybeColor?: string
+  /** Taken from the Here's meta because the author has no profile (legacy entries) */
+  fromMeta: boolean
+}
+
+/** An emoji reaction on a Here */
+export interface ReactInput {
+  /** Session link of any version of the Here */
+  hereLinkAh: ActionHashB64
+  emoji: string
+}
+
+/** Agents who reacted to a Here with an emoji */
+export interface ReactionOutput {
+  emoji: string
+  agents: AgentPubKeyB64[]
+}
+
+/** How often online agents check for due sessions */
+export const SCHEDULED_SESSIONS_CRON = "0 */10 * * * * *";
+
+/**  */
+export interface CreateSessionScheduleInput {
+  spaceEh: EntryHashB64
+  period: SchedulePeriod
+  startsAt: Timestamp
+  name: string
 }
 
 /**
@@ -193,6 +409,7 @@ export enum MessageType {
 	DeleteHere = 'DeleteHere',
 	UpdateHere = 'UpdateHere',
 	NewSession = 'NewSession',
+	NewComment = 'NewComment',
 	NewSpace = 'NewSpace',
 	NewTemplate = 'NewTemplate',
 	NewSvgMarker = 'NewSvgMarker',
@@ -205,6 +422,7 @@ export type Message =
  | {type: "DeleteHere", content: [EntryHashB64, ActionHashB64]}
  | {type: "UpdateHere", content: [number, ActionHashB64, Here]}
  | {type: "NewSession", content: [EntryHashB64, PlacementSession]}
+ | {type: "NewComment", content: CommentOutput}
  | {type: "NewSpace", content: EntryHashB64}
  | {type: "NewTemplate", content: EntryHashB64}
  | {type: "NewSvgMarker", content: EntryHashB64}
@@ -224,10 +442,182 @@ export interface NotifyInput {
   peers: AgentPubKeyB64[]
 }
 
+/**
+ * warning: partial duplicate of playset_integrity::Space
+ * Only holds the fields this zome needs, the other fields are ignored when decoding.
+ */
+export interface SpaceInfo {
+  name: string
+  surface: string
+  zones: Zone[]
+  maybeCoordinateSystem?: CoordinateSystem
+}
+
+/** warning: duplicate of playset::SubSpaceOutput */
+export interface SubSpaceOutput {
+  spaceEh: EntryHashB64
+  attachment: SpaceAttachment
+}
+
+/** Heres in the current session of a sub-space */
+export interface SubSpaceRollup {
+  spaceEh: EntryHashB64
+  attachment: SpaceAttachment
+  /** Session with the highest index, if any */
+  maybeSessionEh?: EntryHashB64
+  hereCount: number
+}
+
+/** Meta key of the emoji of a Here */
+export const EMOJI_META_KEY = "emoji";
+
+/** Meta key of the tag of a Here */
+export const TAG_META_KEY = "tag";
+
+/** Aggregates of the Heres of a session */
+export interface SessionStats {
+  coordinateSystem: CoordinateSystem
+  count: number
+  /** Mean position of the Heres with a position */
+  maybeCentroid?: Coord
+  /** Root mean square distance to the centroid, in km on geo spaces */
+  maybeSpread?: number
+  zoneCounts: Record<string, string>
+  emojiCounts: Record<string, string>
+  tagCounts: Record<string, string>
+}
+
+/**  */
+export interface CompareSessionsInput {
+  fromSessionEh: EntryHashB64
+  toSessionEh: EntryHashB64
+}
+
+/** How the aggregates shifted from one session to another */
+export interface SessionStatsComparison {
+  from: SessionStats
+  to: SessionStats
+  countDelta: number
+  /** Distance between the two centroids */
+  maybeCentroidShift?: number
+  maybeSpreadDelta?: number
+  zoneDeltas: Record<string, string>
+  emojiDeltas: Record<string, string>
+  tagDeltas: Record<string, string>
+}
+
+/** Number of votes for a zone or an item */
+export interface TallyEntry {
+  name: string
+  votes: number
+}
+
+/** Votes of a session ranked by zone and by item (the `tag` meta of the Heres), most voted first */
+export interface VoteTally {
+  total: number
+  zones: TallyEntry[]
+  items: TallyEntry[]
+}
+
+/**  */
+export interface ZoneOccupants {
+  zoneName: string
+  agents: AgentPubKeyB64[]
+}
+
+/**  */
+export interface SessionZonesOutput {
+  zones: ZoneOccupants[]
+  outside: AgentPubKeyB64[]
+}
+
 export interface PlacementSession {
   name: string
   index: number
   spaceEh: EntryHashB64
+  /** Heres created before this time are rejected */
+  maybeOpensAt?: Timestamp
+  /** Heres created after this time are rejected */
+  maybeClosesAt?: Timestamp
+  /** Voting mode: each Here is a vote and an agent can have at most this many Heres in the session */
+  maybeVoteBudget?: number
+}
+
+/** Comment on a Here, possibly replying to another comment on the same Here */
+export interface HereComment {
+  /** Identity of the Here: ActionHash of the session link of its first version */
+  hereLinkAh: ActionHashB64
+  text: string
+  /** ActionHash of the comment replied to */
+  maybeReplyTo?: ActionHashB64
+}
+
+/** Scale then translate applied to the cartesian positions of a session's Heres */
+export interface HereTransform {
+  scaleX: number
+  scaleY: number
+  translateX: number
+  translateY: number
+}
+
+/**
+ * Overlay migrating the positions of the Heres of a session, e.g. after its space's surface was resized.
+ * The Here entries are left untouched.
+ */
+export interface SessionTransform {
+  sessionEh: EntryHashB64
+  /** Creation of the session by the transform's author: only a session's creator can migrate it */
+  sessionAh: ActionHashB64
+  transform: HereTransform
+}
+
+/** Root path of the anchors of attached HRLs */
+export const ATTACHABLES_ROOT_PATH = "attachables";
+
+/**
+ * HRL attached to a Here, carried in the tag of the attachment links.
+ * Same shape as the HrlWithContext of the We applets, with the context JSON-serialized.
+ */
+export interface HrlWithContext {
+  hrl: Hrl
+  context?: string
+}
+
+/**
+ * Frozen copy of the Heres of a session.
+ * A session with a snapshot is closed, but only by the coordinator: see `is_session_closed` in the where zome.
+ */
+export interface SessionSnapshot {
+  sessionEh: EntryHashB64
+  /** Creation of the session by the snapshot's author: only a session's creator can close it */
+  sessionAh: ActionHashB64
+  heres: SnapshotHere[]
+}
+
+export interface SnapshotHere {
+  value: string
+  meta: Record<string, string>
+  author: AgentPubKeyB64
+  linkAh: ActionHashB64
+}
+
+/** Recurring creation of sessions on a space */
+export interface SessionSchedule {
+  spaceEh: EntryHashB64
+  period: SchedulePeriod
+  /** Opening time of the first session */
+  startsAt: Timestamp
+  /** Sessions are named \"<name> <occurrence number>\" */
+  name: string
+  /** Index of the first session, the nth occurrence gets `first_index + n` */
+  firstIndex: number
+}
+
+export type SchedulePeriod =
+  | {daily: null} | {weekly: null};
+export enum SchedulePeriodType {
+	Daily = 'Daily',
+	Weekly = 'Weekly',
 }
 
 /** Here entry definition */
@@ -235,6 +625,38 @@ export interface Here {
   value: string
   sessionEh: EntryHashB64
   meta: Record<string, string>
+  /** The Here is ignored after this time */
+  maybeExpiresAt?: Timestamp
+  /**
+   * Session link of the first version of this Here, when it replaces a previous version.
+   * Comments, reactions and attachments are keyed on it so they survive updates.
+   */
+  maybeOriginAh?: ActionHashB64
+}
+
+/** Summary of a Here stored in the tag of its session link, checked against the Here by validation */
+export interface HereSummary {
+  value: string
+  meta: Record<string, string>
+  maybeExpiresAt?: Timestamp
+  maybeOriginAh?: ActionHashB64
+}
+
+/** Max size in bytes of a summary in a link tag */
+export const MAX_SUMMARY_TAG_SIZE = 800;
+
+/** Format of `Here.value` on geo spaces */
+export interface GeoCoord {
+  lat: number
+  lon: number
+}
+
+/**
+ * warning: partial duplicate of playset_integrity::Space, not an entry type of this zome
+ * Used to decode the fields of a Space needed by this zome, the other fields are ignored.
+ */
+export interface SpaceCoordinates {
+  maybeCoordinateSystem?: CoordinateSystem
 }
 
 /**
@@ -252,11 +674,19 @@ export const WHERE_DEFAULT_INTEGRITY_ZOME_NAME = "where_integrity";
 export enum WhereEntryType {
 	Here = 'Here',
 	PlacementSession = 'PlacementSession',
+	SessionSchedule = 'SessionSchedule',
+	SessionSnapshot = 'SessionSnapshot',
+	HereComment = 'HereComment',
+	SessionTransform = 'SessionTransform',
 }
 export type WhereEntryVariantHere = {Here: Here}
 export type WhereEntryVariantPlacementSession = {PlacementSession: PlacementSession}
+export type WhereEntryVariantSessionSchedule = {SessionSchedule: SessionSchedule}
+export type WhereEntryVariantSessionSnapshot = {SessionSnapshot: SessionSnapshot}
+export type WhereEntryVariantHereComment = {HereComment: HereComment}
+export type WhereEntryVariantSessionTransform = {SessionTransform: SessionTransform}
 export type WhereEntry = 
- | WhereEntryVariantHere | WhereEntryVariantPlacementSession;
+ | WhereEntryVariantHere | WhereEntryVariantPlacementSession | WhereEntryVariantSessionSchedule | WhereEntryVariantSessionSnapshot | WhereEntryVariantHereComment | WhereEntryVariantSessionTransform;
 
 /**
  * -------------------------------------------------------------------------------------------------
@@ -265,8 +695,54 @@ export type WhereEntry =
  * List of all link kinds handled by this Zome
  */
 export type WhereLinkType =
-  | {All: null} | {Hide: null};
+  | {All: null} | {Hide: null} | {HereIndex: null} | {Carried: null} | {Schedules: null} | {Snapshot: null} | {Comments: null} | {Reactions: null} | {Transforms: null} | {HereAttachments: null} | {AttachedHeres: null} | {HereUpdates: null} | {Expiring: null};
 export enum WhereLinkTypeType {
 	All = 'All',
 	Hide = 'Hide',
+	HereIndex = 'HereIndex',
+	Carried = 'Carried',
+	Schedules = 'Schedules',
+	Snapshot = 'Snapshot',
+	Comments = 'Comments',
+	Reactions = 'Reactions',
+	Transforms = 'Transforms',
+	HereAttachments = 'HereAttachments',
+	AttachedHeres = 'AttachedHeres',
+	HereUpdates = 'HereUpdates',
+	Expiring = 'Expiring',
+}
+
+/** Max size in bytes of a reaction emoji */
+export const MAX_REACTION_SIZE = 32;
+
+/**
+ * Where a sub-space is attached on the surface of its parent space.
+ * Carried in the tag of the links between the two spaces.
+ */
+export enum SpaceAttachmentType {
+	Whole = 'Whole',
+	Zone = 'Zone',
+	Point = 'Point',
+}
+export type SpaceAttachment = unknown
+
+/** How the value of a Here placed on a Space is interpreted */
+export type CoordinateSystem =
+  | {cartesian: null} | {geo: null};
+export enum CoordinateSystemType {
+	Cartesian = 'Cartesian',
+	Geo = 'Geo',
+}
+
+/** Named region of a Space's surface */
+export interface Zone {
+  name: string
+  shape: ZoneShape
+}
+
+/** Zone outline in surface coordinates */
+export enum ZoneShapeType {
+	Rect = 'Rect',
+	Polygon = 'Polygon',
 }
+export type ZoneShape = unknown
@@ -12,7 +12,7 @@ import {PlaysetZvm} from "./playset.zvm";
 import {WhereZvm} from "./where.zvm";
 import {dematerializeSpace, SpaceMat} from "./playset.perspective";
 import {Space} from "../bindings/playset.types";
-import {Message, MessageType, PlacementSession, SignalPayload, WHERE_DEFAULT_ROLE_NAME} from "../bindings/where.types";
+import {AuthorProfile, Message, MessageType, PlacementSession, SignalPayload, WHERE_DEFAULT_ROLE_NAME} from "../bindings/where.types";
 import {AgentPubKeyB64, EntryHashB64, AppSignal, AppSignalCb} from "@holochain/client";
 import {ProfilesZvm} from "@ddd-qc/profiles-dvm";
 import {Hrl, HrlWithContext} from "@lightningrodlabs/we-applet";
@@ -137,7 +137,7 @@ export class WhereDvm extends DnaViewModel {
         break;
       case MessageType.NewHere:
         const hereInfo = signalPayload.message.content;
-        const newLocInfo: LocationInfo = materializeHere(hereInfo);
+        const newLocInfo: LocationInfo = materializeHere({...hereInfo, maybeProfile: this.authorProfile(hereInfo.author)});
         if (signalPayload.maybeSpaceHash && this._plays[signalPayload.maybeSpaceHash]) {
           //console.log("locations before add", this._plays[signal.maybeSpaceHash].sessions[hereInfo.entry.sessionEh].locations.length)
           this.whereZvm.addLocation(newLocInfo);
@@ -160,6 +160,7 @@ export class WhereDvm extends DnaViewModel {
           entry: signalPayload.message.content[2],
           linkAh: signalPayload.message.content[1],
           author: signalPayload.from,
+          maybeProfile: this.authorProfile(signalPayload.from),
           };
         const newInfo = materializeHere(newHereInfo);
         if (signalPayload.maybeSpaceHash && this._plays[signalPayload.maybeSpaceHash]) {
@@ -171,6 +172,16 @@ export class WhereDvm extends DnaViewModel {
   }
 
 
+  /** Identity of a Here's author from the profiles zome */
+  private authorProfile(agent: AgentPubKeyB64): AuthorProfile | undefined {
+    const profile = this.profilesZvm.getProfile(agent);
+    if (!profile) {
+      return undefined;
+    }
+    return {nickname: profile.nickname, maybeAvatar: profile.fields.avatar, maybeColor: profile.fields.color, fromMeta: false};
+  }
+
+
   /** */
   async notifyPeers(signal: SignalPayload, peers: Array<AgentPubKeyB64>): Promise<void> {
     // if (signal.message.type != "Ping" && signal.message.type != "Pong") {
@@ -1,4 +1,4 @@
-import {Here, HereOutput, PlacementSession} from "../bindings/where.types";
+import {AuthorProfile, Here, HereOutput, PlacementSession} from "../bindings/where.types";
 import {MarkerType, SpaceMat} from "./playset.perspective";
 import {mapReplacer, mapReviver} from "../utils";
 import {ActionHashB64, AgentPubKeyB64, EntryHashB64} from "@holochain/client";
@@ -119,6 +119,12 @@ export function materializeHere(info: HereInfo) : LocationInfo {
     console.error("Failed parsing meta field into LocationMeta")
     console.error(e)
   }
+  /* Author identity comes from the profiles zome, not from the Here */
+  if (info.maybeProfile) {
+    locationMeta.authorName = info.maybeProfile.nickname;
+    locationMeta.img = info.maybeProfile.maybeAvatar;
+    locationMeta.color = info.maybeProfile.maybeColor;
+  }
   //
   return {
     location: {
@@ -132,11 +138,15 @@ export function materializeHere(info: HereInfo) : LocationInfo {
 }
 
 
+/** Meta keys of the author's identity. Not stored in Heres, see materializeHere() */
+export const AUTHOR_META_KEYS = ["authorName", "img", "color"];
+
+
 /** */
 export function dematerializeHere(location: WhereLocation) : Here {
   let meta: Dictionary<string> = {};
   for (const [key, value] of Object.entries(location.meta)) {
-    if (value === undefined) {continue;}
+    if (value === undefined || AUTHOR_META_KEYS.includes(key)) {continue;}
     meta[key] = JSON.stringify(value, mapReplacer)
   }
   return {
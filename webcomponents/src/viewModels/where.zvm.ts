@@ -169,7 +169,7 @@ export class WhereZvm extends ZomeViewModel {
 
   /** */
   private async probeLocations(sessionEh: EntryHashB64): Promise<Array<LocationInfo>> {
-    const hereInfos = await this.zomeProxy.getHeres(sessionEh);
+    const hereInfos = await this.zomeProxy.getHeresWithOptions({sessionEh, withProfiles: true});
     //console.debug({hereInfos})
     const locs = hereInfos.map((info: HereInfo) => {
       return materializeHere(info)